@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use diesel::{
     r2d2::{ConnectionManager, Pool},
@@ -26,6 +26,16 @@ impl health::Indicator for MyHealthIndicator {
     }
 }
 
+// Custom health indicator from an async function
+#[health::indicator(name = "my_function_indicator")]
+async fn my_function_indicator(up: Arc<bool>) -> Result<(), String> {
+    if *up {
+        Ok(())
+    } else {
+        Err("Something fishy is going on".to_string())
+    }
+}
+
 #[tokio::main]
 async fn main() {
     let rocket = rocket::build();
@@ -33,6 +43,8 @@ async fn main() {
     let my_indicator_up = Box::new(MyHealthIndicator { up: true });
     let my_indicator_down = Box::new(MyHealthIndicator { up: false });
 
+    let my_closure_indicator = health::indicator_fn("my_closure_indicator", || async { Ok(()) });
+
     let manager = ConnectionManager::<SqliteConnection>::new("test.db");
     let pool = Arc::new(
         Pool::builder()
@@ -46,15 +58,38 @@ async fn main() {
             Ok(pool.get().map_err(|e| e.to_string())?)
         });
 
-    actuate(rocket)
-        .with_liveness(health::check::Liveness::new(vec![
-            my_indicator_up,
-            Box::new(diesel_indicator.clone()),
-        ]))
-        .with_readiness(health::check::Readiness::new(vec![
-            my_indicator_down,
-            Box::new(diesel_indicator),
-        ]))
+    // Evaluate the diesel indicator at most once every 5 seconds, even though
+    // it is part of both liveness and readiness
+    let actuator = actuate(rocket)
+        .with_shared_indicator(Box::new(diesel_indicator), Duration::from_secs(5))
+        .unwrap();
+    let diesel_indicator = actuator.shared_indicator("diesel").unwrap();
+
+    let actuator = actuator
+        .with_liveness(
+            health::check::Liveness::new(vec![
+                my_indicator_up,
+                my_closure_indicator,
+                my_function_indicator(Arc::new(true)),
+                Box::new(diesel_indicator.clone()),
+            ])
+            .unwrap(),
+        )
+        .with_readiness(
+            health::check::Readiness::new(vec![my_indicator_down, Box::new(diesel_indicator)])
+                .unwrap()
+                // Log indicator status changes
+                .with_listener(Box::new(health::LogListener)),
+        );
+
+    // Keep readiness down until the cache is warmed up
+    let cache_gate = actuator.with_readiness_gate("cache").unwrap();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        cache_gate.complete();
+    });
+
+    actuator
         .get()
         .ignite()
         .await
@@ -0,0 +1,102 @@
+//! Procedural macros for [valensas-actuator](https://github.com/Valensas/rust-actuator).
+//!
+//! Use through the `health-macros` feature of `valensas-actuator` instead of
+//! depending on this crate directly.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, FnArg, ItemFn, LitStr, Pat};
+
+/// Turns an async function returning `Result<(), String>` or an
+/// `IndicatorResult` into a function returning a `Box<dyn Indicator>`.
+///
+/// The indicator is named after the function unless a name is given with
+/// `#[indicator(name = "...")]`. The arguments of the function are captured
+/// by the indicator and cloned for each check, shared state such as
+/// connection pools should be wrapped in an `Arc`.
+///
+/// ```ignore
+/// #[indicator(name = "database")]
+/// async fn database(pool: Arc<Pool>) -> Result<(), String> {
+///     pool.get().map(|_| ()).map_err(|e| e.to_string())
+/// }
+///
+/// let indicator: Box<dyn Indicator> = database(pool);
+/// ```
+#[proc_macro_attribute]
+pub fn indicator(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut name: Option<LitStr> = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported indicator attribute, expected `name`"))
+        }
+    });
+    parse_macro_input!(args with parser);
+
+    let function = parse_macro_input!(item as ItemFn);
+
+    match expand(name, function) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(name: Option<LitStr>, function: ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let signature = &function.sig;
+
+    if signature.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(
+            signature.fn_token,
+            "indicator functions must be async",
+        ));
+    }
+    if !signature.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &signature.generics,
+            "indicator functions cannot be generic",
+        ));
+    }
+
+    let mut args = vec![];
+    for input in &signature.inputs {
+        let FnArg::Typed(arg) = input else {
+            return Err(syn::Error::new_spanned(
+                input,
+                "indicator functions cannot take self",
+            ));
+        };
+        let Pat::Ident(ident) = arg.pat.as_ref() else {
+            return Err(syn::Error::new_spanned(
+                &arg.pat,
+                "indicator function arguments must be identifiers",
+            ));
+        };
+        args.push((ident, arg.ty.clone()));
+    }
+
+    let ident = &signature.ident;
+    let name = name.unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+    let attrs = &function.attrs;
+    let vis = &function.vis;
+    let block = &function.block;
+    let output = &signature.output;
+    // The inner function keeps the original patterns, e.g. `mut`, while the
+    // outer function only needs the names
+    let arg_patterns: Vec<_> = args.iter().map(|(pattern, _)| pattern).collect();
+    let arg_names: Vec<_> = args.iter().map(|(pattern, _)| &pattern.ident).collect();
+    let arg_types: Vec<_> = args.iter().map(|(_, ty)| ty).collect();
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis fn #ident(#(#arg_names: #arg_types),*) -> ::std::boxed::Box<dyn ::valensas_actuator::health::Indicator> {
+            async fn #ident(#(#arg_patterns: #arg_types),*) #output #block
+
+            ::valensas_actuator::health::indicator_fn(#name, move || {
+                #ident(#(::std::clone::Clone::clone(&#arg_names)),*)
+            })
+        }
+    })
+}
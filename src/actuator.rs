@@ -3,20 +3,116 @@ use rocket::{Build, Rocket};
 use crate::Actuator;
 
 pub trait RocketConfigurerer {
-    fn configure(self, rocket: Rocket<Build>) -> Rocket<Build>;
+    /// Configures the rocket instance. Routes are expected to be mounted
+    /// under the given base path of the actuator.
+    fn configure(self, rocket: Rocket<Build>, base_path: &str) -> Rocket<Build>;
+}
+
+/// A configurer applied when the rocket instance is built, so that it uses
+/// the final base path of the actuator.
+pub(crate) type Configurer = Box<dyn FnOnce(Rocket<Build>, &str) -> Rocket<Build> + Send + Sync>;
+
+/// Joins the base path of the actuator and the path of an endpoint.
+#[cfg(any(feature = "health", feature = "prometheus-rocket"))]
+pub(crate) fn join_path(base_path: &str, path: &str) -> String {
+    let base_path = base_path.trim_end_matches('/');
+    let path = path.trim_start_matches('/');
+
+    match (base_path.is_empty(), path.is_empty()) {
+        (true, true) => "/".to_string(),
+        (_, true) => base_path.to_string(),
+        _ => format!("{}/{}", base_path, path),
+    }
 }
 
 impl Actuator {
     pub fn new(rocket: Rocket<Build>) -> Self {
-        Self { rocket }
+        Self {
+            rocket,
+            base_path: "/".to_string(),
+            configurers: vec![],
+            #[cfg(feature = "health")]
+            indicators: Default::default(),
+            #[cfg(feature = "health")]
+            gates: crate::health::check::ReadinessGates::new("readiness_gates".to_string()),
+            #[cfg(feature = "health")]
+            gates_registered: Default::default(),
+            #[cfg(feature = "health")]
+            readiness: None,
+            #[cfg(feature = "supervisor")]
+            supervisor: Default::default(),
+        }
+    }
+
+    /// Returns the supervisor of the critical background tasks of the
+    /// application.
+    #[cfg(feature = "supervisor")]
+    pub fn task_supervisor(&self) -> crate::supervisor::TaskSupervisor {
+        self.supervisor.clone()
+    }
+
+    /// Sets the path all actuator endpoints are mounted under, e.g.
+    /// `/actuator`. Applies to all endpoints, including the ones configured
+    /// before.
+    pub fn with_base_path(mut self, base_path: &str) -> Actuator {
+        self.base_path = base_path.to_string();
+        self
     }
 
-    pub fn with_configurer<T: RocketConfigurerer>(mut self, configurer: T) -> Actuator {
-        self.rocket = configurer.configure(self.rocket);
+    /// Adds a configurer, applied with the base path of the actuator when
+    /// the rocket instance is returned by [`Actuator::get`].
+    pub fn with_configurer<T: RocketConfigurerer + Send + Sync + 'static>(
+        mut self,
+        configurer: T,
+    ) -> Actuator {
+        self.configurers.push(Box::new(move |rocket, base_path| {
+            configurer.configure(rocket, base_path)
+        }));
         self
     }
 
     pub fn get(self) -> Rocket<Build> {
-        self.rocket
+        let base_path = self.base_path;
+        self.configurers
+            .into_iter()
+            .fold(self.rocket, |rocket, configure| {
+                configure(rocket, &base_path)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(any(feature = "health", feature = "prometheus-rocket"))]
+    fn joins_paths() {
+        use super::join_path;
+
+        assert_eq!(join_path("/", "/"), "/");
+        assert_eq!(join_path("/", ""), "/");
+        assert_eq!(join_path("", "/health"), "/health");
+        assert_eq!(join_path("/", "/health"), "/health");
+        assert_eq!(join_path("/actuator/", "health/"), "/actuator/health/");
+        assert_eq!(join_path("/actuator", "/"), "/actuator");
+    }
+
+    #[test]
+    #[cfg(feature = "health")]
+    fn applies_base_path_to_all_endpoints() {
+        use rocket::{http::Status, local::blocking::Client};
+
+        use crate::{actuate, health::check::Liveness};
+
+        let rocket = actuate(rocket::build())
+            .with_liveness(Liveness::new(vec![]).unwrap())
+            .with_base_path("/actuator")
+            .with_overrides(Default::default(), "token".to_string())
+            .get();
+        let client = Client::tracked(rocket).unwrap();
+
+        let status = |path: &str| client.get(path).dispatch().status();
+        assert_eq!(status("/actuator/health/liveness"), Status::Ok);
+        assert_eq!(status("/actuator/health/overrides"), Status::Unauthorized);
+        assert_eq!(status("/health/liveness"), Status::NotFound);
     }
 }
@@ -0,0 +1,30 @@
+use std::{ffi::CString, io, os::unix::ffi::OsStrExt, path::Path};
+
+/// Space of the filesystem containing a path, in bytes.
+#[derive(Clone, Copy)]
+pub(crate) struct DiskUsage {
+    /// The size of the filesystem.
+    pub(crate) total: u64,
+    /// The space available to unprivileged users.
+    pub(crate) free: u64,
+}
+
+/// Returns the space of the filesystem containing the given path using
+/// statvfs.
+pub(crate) fn usage(path: &Path) -> io::Result<DiskUsage> {
+    let path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: path is a valid null terminated string and stat is a valid
+    // statvfs struct to write to.
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    #[allow(clippy::unnecessary_cast)]
+    Ok(DiskUsage {
+        total: stat.f_blocks as u64 * stat.f_frsize as u64,
+        free: stat.f_bavail as u64 * stat.f_frsize as u64,
+    })
+}
@@ -1,35 +1,138 @@
-use std::collections::HashMap;
+use std::{
+    collections::BTreeMap,
+    sync::{atomic::Ordering, Arc},
+    time::{Duration, SystemTime},
+};
 
-use crate::Actuator;
+use crate::{auth::AccessPolicy, Actuator};
 
-use rocket::{http::Status, serde::json::Json};
-use serde::Serialize;
+use rocket::serde::json::{serde_json::Map, Value};
+use serde::{Deserialize, Serialize};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
 
 pub mod check;
+mod function;
+pub mod listener;
+pub mod overrides;
+mod response;
+pub mod retry;
+pub mod shared;
 
-#[derive(Serialize)]
+pub use function::{indicator_fn, FnIndicator};
+#[cfg(feature = "health-macros")]
+pub use valensas_actuator_macros::indicator;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum IndicatorStatus {
     Up,
+    /// Not evaluated, e.g. because a dependency of the indicator is down.
+    /// Does not fail the health group.
+    Unknown,
+    /// Working with reduced functionality or performance. Does not fail
+    /// the health group.
+    Degraded,
+    /// Intentionally taken out of service, e.g. for maintenance.
+    OutOfService,
     Down,
 }
 
-#[derive(Serialize)]
+impl IndicatorStatus {
+    /// Orders statuses by severity, the status of a health group is the
+    /// most severe status of its indicators.
+    fn severity(&self) -> u8 {
+        match self {
+            Self::Up => 0,
+            Self::Unknown => 1,
+            Self::Degraded => 2,
+            Self::OutOfService => 3,
+            Self::Down => 4,
+        }
+    }
+
+    /// Whether the status fails the health group.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::OutOfService | Self::Down)
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
 pub struct IndicatorResult {
     status: IndicatorStatus,
     message: Option<String>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    details: Map<String, Value>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    overridden: bool,
+}
+
+impl IndicatorResult {
+    /// Creates a result with the given status and no message.
+    pub fn new(status: IndicatorStatus) -> Self {
+        Self {
+            status,
+            message: None,
+            details: Map::new(),
+            overridden: false,
+        }
+    }
+
+    /// Creates an up result.
+    pub fn up() -> Self {
+        Self::new(IndicatorStatus::Up)
+    }
+
+    /// Creates a down result with the given message.
+    pub fn down(message: String) -> Self {
+        Self::new(IndicatorStatus::Down).with_message(message)
+    }
+
+    pub fn with_message(mut self, message: String) -> Self {
+        self.message = Some(message);
+        self
+    }
+
+    /// Adds a detail to the result, e.g. a measured latency or a
+    /// remaining capacity.
+    pub fn with_detail<V: Into<Value>>(mut self, key: &str, value: V) -> Self {
+        self.details.insert(key.to_string(), value.into());
+        self
+    }
+
+    pub fn status(&self) -> IndicatorStatus {
+        self.status
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    pub fn details(&self) -> &Map<String, Value> {
+        &self.details
+    }
+
+    /// Whether the status was set by an administrative override instead of
+    /// evaluating the indicator.
+    pub fn is_overridden(&self) -> bool {
+        self.overridden
+    }
 }
 
 impl From<Result<(), String>> for IndicatorResult {
     fn from(value: Result<(), String>) -> Self {
         match value {
-            Ok(()) => Self {
-                status: IndicatorStatus::Up,
-                message: None,
-            },
-            Err(m) => Self {
-                status: IndicatorStatus::Down,
-                message: Some(m),
-            },
+            Ok(()) => Self::up(),
+            Err(m) => Self::down(m),
+        }
+    }
+}
+
+impl From<IndicatorResult> for Result<(), String> {
+    fn from(value: IndicatorResult) -> Self {
+        match value.status {
+            status if !status.is_failure() => Ok(()),
+            status => Err(value
+                .message
+                .unwrap_or_else(|| format!("indicator is {:?}", status))),
         }
     }
 }
@@ -38,34 +141,147 @@ impl From<Result<(), String>> for IndicatorResult {
 pub trait Indicator: Send + Sync {
     fn name(&self) -> &str;
     async fn check(&self) -> Result<(), String>;
+
+    /// Checks the indicator and returns a detailed result. Defaults to
+    /// the result of [`Indicator::check`] without any details.
+    async fn health(&self) -> IndicatorResult {
+        self.check().await.into()
+    }
 }
 
 #[async_trait::async_trait]
-pub trait Check {
-    fn indicators(&self) -> &Vec<Box<dyn Indicator>>;
+impl<T: Indicator + ?Sized> Indicator for Arc<T> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
 
-    async fn check(
-        &self,
-    ) -> rocket::response::status::Custom<Json<HashMap<&str, IndicatorResult>>> {
-        let mut status = Status::Ok;
+    async fn check(&self) -> Result<(), String> {
+        (**self).check().await
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        (**self).health().await
+    }
+}
 
-        let mut response: HashMap<&str, IndicatorResult> =
-            HashMap::with_capacity(self.indicators().len());
+/// The result of evaluating a health group. Rendered in the format
+/// requested by the `Accept` header of the request:
+///
+/// - `application/health+json`: the IETF
+///   [health check response format](https://datatracker.ietf.org/doc/html/draft-inadarei-api-health-check),
+///   reporting each detail of an indicator as a `<indicator>:<detail>` check
+///   with an `observedValue`
+///
+/// - `application/vnd.spring-boot.actuator.v3+json`: the Spring Boot actuator format
+///
+/// - `text/plain`: a human readable summary
+///
+/// - anything else: a JSON object of indicator results keyed by indicator name
+///
+/// Indicators are ordered by name in all formats. When details are hidden,
+/// only the overall status is included, e.g. `{"status": "Up"}` in the
+/// default JSON format.
+pub struct HealthResponse {
+    status: IndicatorStatus,
+    indicators: BTreeMap<String, IndicatorResult>,
+    time: SystemTime,
+    /// Whether the indicators are hidden, responding only with the overall
+    /// status.
+    details_hidden: bool,
+}
+
+/// Who sees the indicators of a health group, including their messages and
+/// details. Callers who may not see them only get the overall status, with
+/// the same HTTP status code.
+pub enum ShowDetails {
+    Never,
+    /// Only to requests satisfying the given policy.
+    WhenAuthorized(Box<dyn AccessPolicy>),
+    Always,
+}
 
-        let check_futures =
-            futures::future::join_all(self.indicators().iter().map(|i| i.check())).await;
+impl ShowDetails {
+    pub fn when_authorized(policy: impl AccessPolicy + 'static) -> Self {
+        Self::WhenAuthorized(Box::new(policy))
+    }
+}
 
-        let indicator_results = self.indicators().iter().zip(check_futures.iter());
+impl HealthResponse {
+    pub fn new(indicators: BTreeMap<String, IndicatorResult>) -> Self {
+        let status = indicators
+            .values()
+            .map(|r| r.status)
+            .max_by_key(IndicatorStatus::severity)
+            .unwrap_or(IndicatorStatus::Up);
 
-        for (i, result) in indicator_results {
-            if let Err(_) = result {
-                status = Status::ServiceUnavailable;
-            };
-            response.insert(i.name(), result.clone().into());
+        Self {
+            status,
+            indicators,
+            time: SystemTime::now(),
+            details_hidden: false,
         }
+    }
+
+    /// Removes the indicators, keeping only the overall status.
+    pub fn without_details(mut self) -> Self {
+        self.indicators.clear();
+        self.details_hidden = true;
+        self
+    }
 
-        rocket::response::status::Custom(status, Json(response))
+    pub fn status(&self) -> IndicatorStatus {
+        self.status
     }
+
+    pub fn indicators(&self) -> &BTreeMap<String, IndicatorResult> {
+        &self.indicators
+    }
+}
+
+/// A change in the status of an indicator, observed while evaluating a
+/// health group.
+#[derive(Debug)]
+pub struct Transition<'a> {
+    /// The name of the health group, e.g. `liveness`.
+    pub group: &'a str,
+    /// The name of the indicator.
+    pub indicator: &'a str,
+    /// The previous status of the indicator, `None` if this is the first
+    /// time the indicator is evaluated.
+    pub old: Option<IndicatorStatus>,
+    /// The new status of the indicator.
+    pub new: IndicatorStatus,
+    /// The message reported by the indicator.
+    pub message: Option<&'a str>,
+}
+
+/// Receives indicator status transitions of health groups. Listeners are
+/// called synchronously after each evaluation, implementations that need
+/// to do more than quick bookkeeping should hand the transition over to
+/// a channel or a spawned task.
+pub trait Listener: Send + Sync {
+    fn on_transition(&self, transition: &Transition);
+}
+
+/// Listener that logs transitions using the `log` crate. Transitions to
+/// up are logged at info level and transitions to any other status at warn
+/// level.
+pub struct LogListener;
+
+#[async_trait::async_trait]
+pub trait Check {
+    fn group(&self) -> &check::Group;
+
+    async fn check(&self) -> HealthResponse {
+        self.group().check().await
+    }
+}
+
+/// Formats a timestamp as RFC 3339, as used in health responses.
+pub(crate) fn format_time(time: SystemTime) -> String {
+    OffsetDateTime::from(time)
+        .format(&Rfc3339)
+        .unwrap_or_default()
 }
 
 impl Actuator {
@@ -73,7 +289,71 @@ impl Actuator {
         self.with_configurer(liveness)
     }
 
-    pub fn with_readiness(self, readiness: check::Readiness) -> Actuator {
+    pub fn with_readiness(mut self, readiness: check::Readiness) -> Actuator {
+        self.readiness = Some(readiness.registry());
         self.with_configurer(readiness)
     }
+
+    /// Registers a startup task, such as cache preloading, that readiness
+    /// waits for. Readiness reports down until the returned gate is
+    /// completed, and stays down if the gate is dropped without completing.
+    /// The first gate adds the `readiness_gates` indicator to readiness.
+    ///
+    /// Fails if readiness is not exposed yet, or if readiness already has
+    /// another indicator named `readiness_gates`.
+    pub fn with_readiness_gate(
+        &self,
+        name: &str,
+    ) -> Result<check::ReadinessGate, check::GateError> {
+        let registry = self
+            .readiness
+            .as_ref()
+            .ok_or(check::GateError::ReadinessNotConfigured)?;
+
+        // Only the first caller registers the indicator, and resets the flag
+        // on failure so that a later call can try again
+        if self
+            .gates_registered
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            if let Err(e) = registry.add(Box::new(self.gates.clone())) {
+                self.gates_registered.store(false, Ordering::Release);
+                return Err(check::GateError::DuplicateIndicator(e));
+            }
+        }
+
+        Ok(self.gates.gate(name))
+    }
+
+    /// Registers an indicator that can be added to several health groups
+    /// while being evaluated at most once per evaluation window. Use
+    /// [`Actuator::shared_indicator`] to get the indicator by name.
+    pub fn with_shared_indicator(
+        mut self,
+        indicator: Box<dyn Indicator>,
+        window: Duration,
+    ) -> Result<Actuator, check::DuplicateIndicator> {
+        let name = indicator.name().to_string();
+        if self.indicators.contains_key(&name) {
+            return Err(check::DuplicateIndicator::new(name));
+        }
+
+        self.indicators.insert(
+            name,
+            Arc::new(shared::SharedIndicator::new(indicator, window)),
+        );
+        Ok(self)
+    }
+
+    /// Returns the shared indicator registered with the given name.
+    pub fn shared_indicator(&self, name: &str) -> Option<Arc<dyn Indicator>> {
+        self.indicators.get(name).cloned()
+    }
+
+    /// Exposes the `/health/overrides` endpoints to manage the given
+    /// overrides. Requests must be authorized with the given bearer token.
+    pub fn with_overrides(self, overrides: overrides::Overrides, token: String) -> Actuator {
+        self.with_configurer(overrides::OverrideEndpoint::new(overrides, token))
+    }
 }
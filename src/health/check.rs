@@ -5,25 +5,188 @@ use ::tonic::transport::Channel;
 #[cfg(feature = "health-tonic")]
 use tonic_health::pb::health_client::HealthClient;
 
-use super::Indicator;
+#[cfg(any(feature = "health-disk", feature = "health-writable"))]
+use std::path::PathBuf;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicBool, AtomicU64},
+        Arc, Mutex, RwLock,
+    },
+    time::{Duration, Instant},
+};
 
+use super::{overrides::Overrides, Indicator, IndicatorStatus, Listener, ShowDetails};
+use crate::auth::AccessPolicy;
+
+#[cfg(feature = "health-command")]
+pub mod command;
+#[cfg(feature = "health-disk")]
+pub mod disk;
+pub mod gate;
+pub mod group;
+pub mod heartbeat;
+#[cfg(feature = "health-http")]
+pub mod http;
+#[cfg(feature = "health-tokio")]
+pub mod lag;
 pub mod liveness;
 pub mod readiness;
+#[cfg(feature = "health-redis")]
+pub mod redis;
+pub mod registry;
+#[cfg(feature = "supervisor")]
+pub mod supervisor;
+#[cfg(feature = "health-tcp")]
+pub mod tcp;
+#[cfg(feature = "health-tls")]
+pub mod tls;
 #[cfg(feature = "health-tonic")]
 pub mod tonic;
+#[cfg(feature = "health-writable")]
+pub mod writable;
 
-/// Liveness probe for server. Exposes `/health/liveness` route that
+/// Liveness probe for server. Exposes `/health/liveness` route, by default, that
 /// returns 200 OK when all indicators are up and 503 Service Unavailable
 /// when at least one indicator is down.
 pub struct Liveness {
-    indicators: Vec<Box<dyn Indicator>>,
+    group: Group,
+    /// The path of the endpoint, relative to the base path of the actuator.
+    path: String,
+    /// The policy requests to the endpoint must satisfy.
+    access_policy: Option<Arc<dyn AccessPolicy>>,
 }
 
-/// Readiness probe for server. Exposes `/health/readiness` route that
+/// Readiness probe for server. Exposes `/health/readiness` route, by default, that
 /// returns 200 OK when all indicators are up and 503 Service Unavailable
 /// when at least one indicator is down.
 pub struct Readiness {
-    indicators: Vec<Box<dyn Indicator>>,
+    group: Group,
+    /// The path of the endpoint, relative to the base path of the actuator.
+    path: String,
+    /// The policy requests to the endpoint must satisfy.
+    access_policy: Option<Arc<dyn AccessPolicy>>,
+}
+
+/// A named set of indicators evaluated together, such as liveness or
+/// readiness. Keeps track of the last status of each indicator to notify
+/// listeners of status transitions.
+pub struct Group {
+    /// The name of the group.
+    name: String,
+    /// The indicators to evaluate.
+    registry: Registry,
+    /// The listeners notified of status transitions.
+    listeners: Vec<Box<dyn Listener>>,
+    /// The administrative overrides applied to the indicators.
+    overrides: Option<Overrides>,
+    /// The names of the indicators each indicator depends on.
+    dependencies: HashMap<String, Vec<String>>,
+    /// The maximum number of indicators evaluated concurrently.
+    concurrency_limit: Option<usize>,
+    /// The priority of each indicator, higher priorities are evaluated
+    /// first when the concurrency limit is reached.
+    priorities: HashMap<String, i32>,
+    /// The maximum duration of the evaluation of the whole group.
+    deadline: Option<Duration>,
+    /// The number of evaluations started, used to order their results.
+    evaluations: AtomicU64,
+    /// The status of each indicator and the evaluation it was observed in,
+    /// as of the latest evaluation.
+    statuses: Mutex<HashMap<String, (u64, IndicatorStatus)>>,
+    /// The startup grace period, if it has not ended yet.
+    grace: Option<Grace>,
+    /// Who sees the indicators of the group.
+    show_details: ShowDetails,
+}
+
+/// Startup grace period of a health group, during which failing indicators
+/// are reported as unknown.
+struct Grace {
+    /// When the grace period ends.
+    until: Instant,
+    /// The indicators that have succeeded at least once.
+    succeeded: Mutex<HashSet<String>>,
+    /// Whether the grace period ended early, as every indicator succeeded.
+    ended: AtomicBool,
+}
+
+/// Handle to the indicators of a health group. Cloned handles share the
+/// same indicators, so indicators can be added or removed while the server
+/// is running, e.g. for connections established after launch.
+#[derive(Clone)]
+pub struct Registry {
+    indicators: Arc<RwLock<Vec<Arc<dyn Indicator>>>>,
+}
+
+/// Error returned when registering an indicator with a name that is
+/// already registered.
+#[derive(Debug)]
+pub struct DuplicateIndicator {
+    /// The name of the indicator.
+    name: String,
+}
+
+/// Error returned when registering a readiness gate.
+#[derive(Debug)]
+pub enum GateError {
+    /// Readiness is not exposed yet.
+    ReadinessNotConfigured,
+    /// Readiness already has another indicator with the name of the gates
+    /// indicator.
+    DuplicateIndicator(DuplicateIndicator),
+}
+
+/// Handle that background workers, such as consumers or schedulers, beat
+/// to signal they are making progress. Cloned handles share the same
+/// heartbeat.
+#[derive(Clone)]
+pub struct Heartbeat {
+    /// Reference point for `last_beat_micros`.
+    started: tokio::time::Instant,
+    /// The time of the last beat, in microseconds since `started`.
+    last_beat_micros: Arc<AtomicU64>,
+}
+
+/// Health indicator for background workers. Reports down when the
+/// heartbeat has not beaten within the configured interval.
+pub struct HeartbeatIndicator {
+    /// The name of the indicator.
+    name: String,
+    /// The heartbeat of the worker.
+    heartbeat: Heartbeat,
+    /// The maximum time between beats.
+    interval: Duration,
+}
+
+/// Health indicator for startup warm-up tasks, such as cache preloading or
+/// connection warm-up. Reports down while any gate is pending and once a
+/// gate has failed. Cloned indicators share the same gates.
+#[derive(Clone)]
+pub struct ReadinessGates {
+    /// The name of the indicator.
+    name: String,
+    /// The outstanding gates, keyed by name.
+    gates: Arc<Mutex<HashMap<String, GateState>>>,
+}
+
+/// Guard of a startup task registered with [`ReadinessGates`]. The task
+/// completes the gate when it has finished, dropping the guard without
+/// completing it marks the gate as failed.
+pub struct ReadinessGate {
+    /// The name of the gate.
+    name: String,
+    /// Identifies this guard, so that it does not resolve a gate registered
+    /// again under the same name.
+    id: u64,
+    /// The gates shared with the indicator.
+    gates: Arc<Mutex<HashMap<String, GateState>>>,
+}
+
+struct GateState {
+    id: u64,
+    since: Instant,
+    failed: bool,
 }
 
 /// Health indicator for tonic-health. Uses the gRPC health protocol to
@@ -56,3 +219,162 @@ where
     /// The query to execute to verify health.
     query: String,
 }
+
+/// Health indicator for disk space. Reports down when the free space of
+/// the filesystem containing the given path is below the threshold, or
+/// when statvfs does not complete within the timeout, e.g. on a hung
+/// network filesystem.
+#[cfg(feature = "health-disk")]
+pub struct DiskSpace {
+    /// The name of the indicator.
+    name: String,
+    /// A path on the filesystem to verify, e.g. a data volume.
+    path: PathBuf,
+    /// The minimum free space.
+    threshold: DiskSpaceThreshold,
+    /// The maximum duration of the statvfs call.
+    timeout: Duration,
+}
+
+/// The minimum free space of a [`DiskSpace`] indicator.
+#[cfg(feature = "health-disk")]
+pub enum DiskSpaceThreshold {
+    /// Minimum free space in bytes.
+    Bytes(u64),
+    /// Minimum free space as a percentage of the filesystem size.
+    Percent(f64),
+}
+
+/// Health indicator for filesystem writability. Creates, fsyncs and
+/// deletes a probe file in the given directory, reporting down when any
+/// of the syscalls fails or the probe does not complete within the timeout,
+/// e.g. after a read-only remount or when inodes are exhausted. A new probe
+/// is not started while the previous one is still blocked.
+#[cfg(feature = "health-writable")]
+pub struct WritableDirectory {
+    /// The name of the indicator.
+    name: String,
+    /// The directory to create the probe file in.
+    directory: PathBuf,
+    /// The maximum duration of the probe.
+    timeout: Duration,
+    /// Whether a probe is still running, possibly after timing out.
+    in_flight: Arc<AtomicBool>,
+}
+
+/// Health indicator for TCP services. Connects to the given address and
+/// optionally sends a payload and verifies the prefix of the response,
+/// reporting down when any step fails or does not complete within the
+/// timeout.
+#[cfg(feature = "health-tcp")]
+pub struct Tcp {
+    /// The name of the indicator.
+    name: String,
+    /// The `host:port` address to connect to.
+    address: String,
+    /// The maximum duration of the connection and the exchange.
+    timeout: Duration,
+    /// The bytes to send after connecting.
+    send: Option<Vec<u8>>,
+    /// The bytes the response is expected to start with.
+    expect: Option<Vec<u8>>,
+}
+
+/// Health indicator for HTTP dependencies, such as the readiness endpoint
+/// of another service. Reports down when the request fails, times out,
+/// returns an unexpected status or, if configured, does not contain the
+/// expected value at a JSON path.
+#[cfg(feature = "health-http")]
+pub struct Http {
+    /// The name of the indicator.
+    name: String,
+    /// The url to request.
+    url: String,
+    /// The maximum duration of the request.
+    timeout: Duration,
+    /// The request method.
+    method: reqwest::Method,
+    /// The expected status codes, any 2xx status if empty.
+    expected_statuses: Vec<u16>,
+    /// The path and value expected in the JSON response body.
+    json_path: Option<(String, rocket::serde::json::Value)>,
+    /// The client to use for requests.
+    client: reqwest::Client,
+}
+
+/// Health indicator for redis. Runs `PING` and optionally verifies the
+/// replication role of the server using `INFO replication`, reporting down
+/// when the commands do not complete within the timeout.
+#[derive(Clone)]
+#[cfg(feature = "health-redis")]
+pub struct Redis<C: ::redis::aio::ConnectionLike + Clone + Send + Sync> {
+    /// The name of the indicator.
+    name: String,
+    /// The connection to run commands on.
+    connection: C,
+    /// The expected replication role, e.g. `master`.
+    expected_role: Option<String>,
+    /// The maximum duration of the commands.
+    timeout: Duration,
+}
+
+/// Health indicator running an external command, e.g. a legacy health
+/// check script. Reports up when the command exits with code 0 and down
+/// for other exit codes or when it does not complete within the timeout.
+/// The trimmed output of the command is used as message.
+#[cfg(feature = "health-command")]
+pub struct ExternalCommand {
+    /// The name of the indicator.
+    name: String,
+    /// The program to run.
+    program: String,
+    /// The arguments of the program.
+    args: Vec<String>,
+    /// The maximum duration of the command.
+    timeout: Duration,
+    /// The statuses reported for specific exit codes.
+    exit_codes: HashMap<i32, IndicatorStatus>,
+}
+
+/// Health indicator for TLS certificate expiry. Loads the certificates of
+/// the given PEM files or PEM data, reporting degraded when a certificate
+/// expires within the warning period and down when a certificate has
+/// expired.
+#[cfg(feature = "health-tls")]
+pub struct TlsCertificate {
+    /// The name of the indicator.
+    name: String,
+    /// The PEM files or data to load certificates from.
+    sources: Vec<CertificateSource>,
+    /// The period before expiry in which certificates are reported as
+    /// degraded.
+    warning: Duration,
+}
+
+#[cfg(feature = "health-tls")]
+pub use crate::tls::CertificateSource;
+
+/// Health indicator for the scheduling delay of the tokio runtime, as
+/// measured by a [`LagMonitor`](crate::lag::LagMonitor).
+#[cfg(feature = "health-tokio")]
+pub struct EventLoopLag {
+    /// The name of the indicator.
+    name: String,
+    /// The monitor measuring the lag.
+    monitor: crate::lag::LagMonitor,
+    /// The lag above which the indicator is degraded.
+    degraded: Duration,
+    /// The lag above which the indicator is down.
+    down: Duration,
+}
+
+/// Health indicator for supervised tasks. Reports down when a critical
+/// task of the [`TaskSupervisor`](crate::supervisor::TaskSupervisor) has
+/// exited, panicked or was cancelled.
+#[cfg(feature = "supervisor")]
+pub struct Supervisor {
+    /// The name of the indicator.
+    name: String,
+    /// The supervisor of the tasks.
+    supervisor: crate::supervisor::TaskSupervisor,
+}
@@ -0,0 +1,232 @@
+use std::{collections::HashMap, io, process::Stdio, time::Duration};
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::health::{self, IndicatorResult, IndicatorStatus};
+
+use super::ExternalCommand;
+
+/// The maximum number of bytes of stdout and of stderr used as message.
+const MAX_OUTPUT: u64 = 4096;
+
+impl ExternalCommand {
+    /// Creates a new command indicator. Exit code 0 is reported as up and
+    /// any other exit code as down.
+    pub fn new(name: String, program: String, args: Vec<String>, timeout: Duration) -> Self {
+        Self {
+            name,
+            program,
+            args,
+            timeout,
+            exit_codes: HashMap::new(),
+        }
+    }
+
+    /// Reports the given status when the command exits with the given code,
+    /// e.g. to report degraded instead of down for a warning exit code.
+    pub fn with_exit_code(mut self, code: i32, status: IndicatorStatus) -> Self {
+        self.exit_codes.insert(code, status);
+        self
+    }
+
+    fn status(&self, code: Option<i32>) -> IndicatorStatus {
+        match code {
+            Some(code) => match self.exit_codes.get(&code) {
+                Some(status) => *status,
+                None if code == 0 => IndicatorStatus::Up,
+                None => IndicatorStatus::Down,
+            },
+            // Terminated by a signal
+            None => IndicatorStatus::Down,
+        }
+    }
+}
+
+/// Reads up to [`MAX_OUTPUT`] bytes of the given pipe, discarding the rest
+/// so that the command does not block on a full pipe.
+async fn read_capped(pipe: Option<impl AsyncRead + Unpin>) -> io::Result<Vec<u8>> {
+    let Some(mut pipe) = pipe else {
+        return Ok(vec![]);
+    };
+
+    let mut output = vec![];
+    (&mut pipe)
+        .take(MAX_OUTPUT)
+        .read_to_end(&mut output)
+        .await?;
+    tokio::io::copy(&mut pipe, &mut tokio::io::sink()).await?;
+
+    Ok(output)
+}
+
+#[async_trait::async_trait]
+impl health::Indicator for ExternalCommand {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.health().await.into()
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        let child = tokio::process::Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn();
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) => {
+                return IndicatorResult::down(format!("could not run {}: {}", self.program, e))
+            }
+        };
+
+        let stdout = read_capped(child.stdout.take());
+        let stderr = read_capped(child.stderr.take());
+        let output = async { tokio::try_join!(child.wait(), stdout, stderr) };
+
+        // The command is killed when the child is dropped on timeout
+        let (status, stdout, stderr) = match tokio::time::timeout(self.timeout, output).await {
+            Ok(Ok(output)) => output,
+            Ok(Err(e)) => {
+                return IndicatorResult::down(format!("could not run {}: {}", self.program, e))
+            }
+            Err(_) => {
+                return IndicatorResult::down(format!(
+                    "{} timed out after {:?}",
+                    self.program, self.timeout
+                ))
+            }
+        };
+
+        let code = status.code();
+        let message = [&stdout, &stderr]
+            .into_iter()
+            .map(|o| String::from_utf8_lossy(o).trim().to_string())
+            .filter(|o| !o.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut result = IndicatorResult::new(self.status(code));
+        if !message.is_empty() {
+            result = result.with_message(message);
+        }
+
+        match code {
+            Some(code) => result.with_detail("exit_code", code),
+            None => result.with_detail("exit_status", status.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::health::{Indicator, IndicatorResult, IndicatorStatus};
+
+    use super::ExternalCommand;
+
+    fn sh(script: &str) -> ExternalCommand {
+        ExternalCommand::new(
+            "command".to_string(),
+            "sh".to_string(),
+            vec!["-c".to_string(), script.to_string()],
+            Duration::from_secs(5),
+        )
+        .with_exit_code(2, IndicatorStatus::Degraded)
+        .with_exit_code(3, IndicatorStatus::OutOfService)
+    }
+
+    fn summary(result: &IndicatorResult) -> (IndicatorStatus, Option<&str>) {
+        (result.status, result.message.as_deref())
+    }
+
+    #[tokio::test]
+    async fn maps_exit_codes() {
+        let up = sh("echo ' all good '").health().await;
+        assert_eq!(summary(&up), (IndicatorStatus::Up, Some("all good")));
+        assert_eq!(up.details["exit_code"], 0);
+
+        for (script, status) in [
+            ("exit 2", IndicatorStatus::Degraded),
+            ("exit 3", IndicatorStatus::OutOfService),
+            ("exit 1", IndicatorStatus::Down),
+        ] {
+            assert_eq!(sh(script).health().await.status, status);
+        }
+
+        let killed = sh("kill -9 $$").health().await;
+        assert_eq!(killed.status, IndicatorStatus::Down);
+        assert!(killed.details.contains_key("exit_status"));
+    }
+
+    #[tokio::test]
+    async fn reports_stdout_and_stderr() {
+        let result = sh("echo warning; echo disk almost full >&2; exit 2")
+            .health()
+            .await;
+        assert_eq!(
+            summary(&result),
+            (IndicatorStatus::Degraded, Some("warning\ndisk almost full"))
+        );
+
+        let result = sh("head -c 100000 /dev/zero | tr '\\0' x").health().await;
+        assert_eq!(result.status, IndicatorStatus::Up);
+        assert_eq!(result.message.unwrap().len(), 4096);
+    }
+
+    #[tokio::test]
+    async fn kills_commands_exceeding_timeout() {
+        let pid_file =
+            std::env::temp_dir().join(format!("actuator-command-{}", std::process::id()));
+        let command = ExternalCommand::new(
+            "command".to_string(),
+            "sh".to_string(),
+            vec![
+                "-c".to_string(),
+                format!("echo $$ > {}; exec sleep 5", pid_file.display()),
+            ],
+            Duration::from_millis(200),
+        );
+
+        let result = command.health().await;
+        assert_eq!(
+            summary(&result),
+            (IndicatorStatus::Down, Some("sh timed out after 200ms"))
+        );
+
+        let pid = std::fs::read_to_string(&pid_file).unwrap();
+        std::fs::remove_file(&pid_file).unwrap();
+        let stat = format!("/proc/{}/stat", pid.trim());
+        let start = Instant::now();
+        // Killed processes may linger as zombies until reaped
+        while std::fs::read_to_string(&stat).is_ok_and(|s| !s.contains(") Z ")) {
+            assert!(
+                start.elapsed() < Duration::from_secs(2),
+                "command was not killed"
+            );
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_missing_program() {
+        let result = ExternalCommand::new(
+            "command".to_string(),
+            "/nonexistent/actuator".to_string(),
+            vec![],
+            Duration::from_secs(5),
+        )
+        .health()
+        .await;
+        assert_eq!(result.status, IndicatorStatus::Down);
+        assert!(result
+            .message
+            .unwrap()
+            .starts_with("could not run /nonexistent/actuator"));
+    }
+}
@@ -0,0 +1,134 @@
+use std::{path::PathBuf, time::Duration};
+
+use crate::{
+    disk,
+    health::{self, IndicatorResult},
+};
+
+use super::{DiskSpace, DiskSpaceThreshold};
+
+impl DiskSpace {
+    /// Creates a new disk space indicator for the filesystem containing the
+    /// given path.
+    pub fn new(name: String, path: PathBuf, threshold: DiskSpaceThreshold) -> Self {
+        Self {
+            name,
+            path,
+            threshold,
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// Sets the maximum duration of the statvfs call. Defaults to 5 seconds.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl DiskSpaceThreshold {
+    fn is_exceeded(&self, free: u64, total: u64) -> bool {
+        match *self {
+            Self::Bytes(bytes) => free < bytes,
+            Self::Percent(percent) => total > 0 && (free as f64 / total as f64) * 100.0 < percent,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl health::Indicator for DiskSpace {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.health().await.into()
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        let path = self.path.clone();
+        let usage = tokio::time::timeout(
+            self.timeout,
+            tokio::task::spawn_blocking(move || disk::usage(&path)),
+        )
+        .await;
+
+        let usage = match usage {
+            Ok(Ok(Ok(usage))) => usage,
+            Ok(Ok(Err(e))) => {
+                return IndicatorResult::down(format!(
+                    "statvfs {} failed: {}",
+                    self.path.display(),
+                    e
+                ))
+            }
+            Ok(Err(e)) => return IndicatorResult::down(format!("statvfs failed: {}", e)),
+            Err(_) => {
+                return IndicatorResult::down(format!(
+                    "statvfs {} timed out after {:?}",
+                    self.path.display(),
+                    self.timeout
+                ))
+            }
+        };
+
+        let result = if self.threshold.is_exceeded(usage.free, usage.total) {
+            IndicatorResult::down(format!(
+                "free disk space of {} is below the threshold",
+                self.path.display()
+            ))
+        } else {
+            IndicatorResult::up()
+        };
+
+        let threshold = match self.threshold {
+            DiskSpaceThreshold::Bytes(bytes) => format!("{} bytes", bytes),
+            DiskSpaceThreshold::Percent(percent) => format!("{}%", percent),
+        };
+
+        result
+            .with_detail("path", self.path.display().to_string())
+            .with_detail("total_bytes", usage.total)
+            .with_detail("free_bytes", usage.free)
+            .with_detail("threshold", threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::health::{Indicator, IndicatorStatus};
+
+    use super::{DiskSpace, DiskSpaceThreshold};
+
+    #[tokio::test]
+    async fn reports_threshold() {
+        let dir = std::env::temp_dir();
+
+        let up = DiskSpace::new(
+            "disk".to_string(),
+            dir.clone(),
+            DiskSpaceThreshold::Bytes(0),
+        );
+        assert_eq!(up.health().await.status, IndicatorStatus::Up);
+
+        let down = DiskSpace::new("disk".to_string(), dir, DiskSpaceThreshold::Bytes(u64::MAX));
+        let result = down.health().await;
+        assert_eq!(result.status, IndicatorStatus::Down);
+        assert!(result.message.unwrap().contains("below the threshold"));
+    }
+
+    #[tokio::test]
+    async fn reports_statvfs_failure() {
+        let missing = DiskSpace::new(
+            "disk".to_string(),
+            "/nonexistent/actuator".into(),
+            DiskSpaceThreshold::Percent(10.0),
+        );
+        let result = missing.health().await;
+        assert_eq!(result.status, IndicatorStatus::Down);
+        assert!(result
+            .message
+            .unwrap()
+            .starts_with("statvfs /nonexistent/actuator failed"));
+    }
+}
@@ -0,0 +1,224 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+use rocket::serde::json::json;
+
+use crate::health::{self, IndicatorResult};
+
+use super::{GateError, GateState, ReadinessGate, ReadinessGates};
+
+static NEXT_GATE_ID: AtomicU64 = AtomicU64::new(0);
+
+impl ReadinessGates {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            gates: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Registers a pending gate with the given name, replacing any gate
+    /// registered under the same name.
+    pub fn gate(&self, name: &str) -> ReadinessGate {
+        let id = NEXT_GATE_ID.fetch_add(1, Ordering::Relaxed);
+
+        self.gates.lock().unwrap_or_else(|e| e.into_inner()).insert(
+            name.to_string(),
+            GateState {
+                id,
+                since: Instant::now(),
+                failed: false,
+            },
+        );
+
+        ReadinessGate {
+            name: name.to_string(),
+            id,
+            gates: self.gates.clone(),
+        }
+    }
+}
+
+impl ReadinessGate {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Resolves the gate.
+    pub fn complete(self) {
+        let mut gates = self.gates.lock().unwrap_or_else(|e| e.into_inner());
+        if gates.get(&self.name).is_some_and(|g| g.id == self.id) {
+            gates.remove(&self.name);
+        }
+    }
+}
+
+impl Drop for ReadinessGate {
+    fn drop(&mut self) {
+        let mut gates = self.gates.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(gate) = gates.get_mut(&self.name).filter(|g| g.id == self.id) {
+            gate.failed = true;
+        }
+    }
+}
+
+impl fmt::Display for GateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReadinessNotConfigured => write!(f, "readiness is not configured"),
+            Self::DuplicateIndicator(e) => write!(f, "cannot report readiness gates: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for GateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ReadinessNotConfigured => None,
+            Self::DuplicateIndicator(e) => Some(e),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl health::Indicator for ReadinessGates {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.health().await.into()
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        let gates = self.gates.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut names: Vec<&String> = gates.keys().collect();
+        names.sort();
+
+        let mut failed = vec![];
+        let mut pending = vec![];
+        for name in &names {
+            if gates[*name].failed {
+                failed.push(name.as_str());
+            } else {
+                pending.push(name.as_str());
+            }
+        }
+
+        let result = if !failed.is_empty() {
+            IndicatorResult::down(format!("failed gates: {}", failed.join(", ")))
+        } else if !pending.is_empty() {
+            IndicatorResult::down(format!("pending gates: {}", pending.join(", ")))
+        } else {
+            IndicatorResult::up()
+        };
+
+        names.into_iter().fold(result, |result, name| {
+            let gate = &gates[name];
+            result.with_detail(
+                name,
+                json!({
+                    "state": if gate.failed { "failed" } else { "pending" },
+                    "pending_ms": gate.since.elapsed().as_millis() as u64,
+                }),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::{http::Status, local::blocking::Client};
+
+    use crate::{
+        actuate,
+        health::{
+            check::{GateError, Readiness},
+            indicator_fn,
+        },
+    };
+
+    fn readiness(client: &Client) -> (Status, String) {
+        let response = client.get("/health/readiness").dispatch();
+        (response.status(), response.into_string().unwrap())
+    }
+
+    #[test]
+    fn reports_gates_after_first_gate() {
+        let actuator = actuate(rocket::build()).with_readiness(Readiness::new(vec![]).unwrap());
+        let registry = actuator.readiness.clone().unwrap();
+        assert!(!registry.contains("readiness_gates"));
+
+        let cache = actuator.with_readiness_gate("cache").unwrap();
+        let warmup = actuator.with_readiness_gate("warmup").unwrap();
+        assert_eq!(registry.names(), vec!["readiness_gates"]);
+
+        let client = Client::tracked(actuator.get()).unwrap();
+        let (status, body) = readiness(&client);
+        assert_eq!(status, Status::ServiceUnavailable);
+        assert!(body.contains("pending gates: cache, warmup"));
+
+        cache.complete();
+        drop(warmup);
+        let (status, body) = readiness(&client);
+        assert_eq!(status, Status::ServiceUnavailable);
+        assert!(body.contains("failed gates: warmup"));
+    }
+
+    #[test]
+    fn rejects_gates_without_readiness() {
+        let actuator = actuate(rocket::build());
+        assert!(matches!(
+            actuator.with_readiness_gate("cache"),
+            Err(GateError::ReadinessNotConfigured)
+        ));
+    }
+
+    #[test]
+    fn rejects_conflicting_indicator() {
+        let conflicting = indicator_fn("readiness_gates".to_string(), || async {
+            Ok::<(), String>(())
+        });
+        let actuator =
+            actuate(rocket::build()).with_readiness(Readiness::new(vec![conflicting]).unwrap());
+        assert!(matches!(
+            actuator.with_readiness_gate("cache"),
+            Err(GateError::DuplicateIndicator(_))
+        ));
+
+        let registry = actuator.readiness.clone().unwrap();
+        registry.remove("readiness_gates");
+        actuator.with_readiness_gate("cache").unwrap();
+        assert!(registry.contains("readiness_gates"));
+    }
+
+    #[test]
+    fn registers_gates_once_when_called_concurrently() {
+        let actuator = actuate(rocket::build()).with_readiness(Readiness::new(vec![]).unwrap());
+
+        std::thread::scope(|s| {
+            let handles: Vec<_> = (0..8)
+                .map(|i| {
+                    let actuator = &actuator;
+                    s.spawn(move || actuator.with_readiness_gate(&format!("task{}", i)))
+                })
+                .collect();
+            for handle in handles {
+                assert!(handle.join().unwrap().is_ok());
+            }
+        });
+
+        assert_eq!(
+            actuator.readiness.clone().unwrap().names(),
+            vec!["readiness_gates"]
+        );
+    }
+}
@@ -0,0 +1,710 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap, HashSet},
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::health::{
+    overrides::Overrides, Check, HealthResponse, Indicator, IndicatorResult, IndicatorStatus,
+    Listener, ShowDetails, Transition,
+};
+
+use futures::StreamExt;
+use rocket::{
+    http::Status,
+    request::{FromRequest, Outcome},
+    Request,
+};
+
+use super::{DuplicateIndicator, Grace, Group, Registry};
+
+impl Group {
+    /// Creates a new group. Fails if several indicators have the same name.
+    pub fn new(
+        name: String,
+        indicators: Vec<Box<dyn Indicator>>,
+    ) -> Result<Self, DuplicateIndicator> {
+        Ok(Self {
+            name,
+            registry: Registry::new(indicators)?,
+            listeners: vec![],
+            overrides: None,
+            dependencies: HashMap::new(),
+            concurrency_limit: None,
+            priorities: HashMap::new(),
+            deadline: None,
+            evaluations: AtomicU64::new(0),
+            statuses: Mutex::new(HashMap::new()),
+            grace: None,
+            show_details: ShowDetails::Always,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns a handle to the indicators of the group.
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Adds a listener notified whenever the status of an indicator in the
+    /// group changes.
+    pub fn add_listener(&mut self, listener: Box<dyn Listener>) {
+        self.listeners.push(listener);
+    }
+
+    /// Applies the given overrides to the indicators of the group.
+    pub fn set_overrides(&mut self, overrides: Overrides) {
+        self.overrides = Some(overrides);
+    }
+
+    /// Declares that the indicator with the given name depends on another
+    /// indicator of the group. The indicator is evaluated after its
+    /// dependency, and is reported as unknown without being evaluated when
+    /// the dependency fails. Dependencies on indicators that are not
+    /// registered are ignored.
+    pub fn add_dependency(&mut self, indicator: &str, dependency: &str) {
+        self.dependencies
+            .entry(indicator.to_string())
+            .or_default()
+            .push(dependency.to_string());
+    }
+
+    /// Limits the number of indicators evaluated concurrently. Further
+    /// indicators are queued in priority order, then in registration order.
+    pub fn set_concurrency_limit(&mut self, limit: usize) {
+        self.concurrency_limit = Some(limit.max(1));
+    }
+
+    /// Sets the priority of the indicator with the given name. Indicators
+    /// with higher priorities are evaluated first when the concurrency limit
+    /// is reached. The default priority is 0.
+    pub fn set_priority(&mut self, indicator: &str, priority: i32) {
+        self.priorities.insert(indicator.to_string(), priority);
+    }
+
+    /// Sets the maximum duration of the evaluation of the group. Indicators,
+    /// including queued ones, that have not completed by the deadline are
+    /// reported as down.
+    pub fn set_deadline(&mut self, deadline: Duration) {
+        self.deadline = Some(deadline);
+    }
+
+    /// Starts a grace period of the given duration, during which failing
+    /// indicators are reported as unknown with the message `starting`, so
+    /// that they do not fail the group while connections are established
+    /// after boot. The grace period ends early once every indicator has
+    /// succeeded at least once.
+    pub fn set_grace_period(&mut self, period: Duration) {
+        self.grace = Some(Grace {
+            until: Instant::now() + period,
+            succeeded: Mutex::new(HashSet::new()),
+            ended: false.into(),
+        });
+    }
+
+    /// Sets who sees the indicators of the group, by default everyone.
+    pub fn set_show_details(&mut self, show_details: ShowDetails) {
+        self.show_details = show_details;
+    }
+
+    /// Whether the given request may see the indicators of the group.
+    pub async fn details_visible(&self, req: &Request<'_>) -> bool {
+        match &self.show_details {
+            ShowDetails::Never => false,
+            ShowDetails::WhenAuthorized(policy) => policy.authorize(req).await.is_ok(),
+            ShowDetails::Always => true,
+        }
+    }
+
+    /// Evaluates the indicators of the group in waves, each wave containing
+    /// the indicators whose dependencies have been evaluated. Indicators
+    /// within a wave are evaluated concurrently, up to the concurrency limit.
+    /// Overridden indicators are not evaluated.
+    pub async fn check(&self) -> HealthResponse {
+        let evaluation = self.evaluations.fetch_add(1, Ordering::Relaxed);
+        let deadline = self.deadline.map(|d| tokio::time::Instant::now() + d);
+        let registered = self.registry.indicators();
+        let names: HashSet<&str> = registered.iter().map(|i| i.name()).collect();
+
+        let mut indicators: BTreeMap<String, IndicatorResult> = BTreeMap::new();
+        let mut remaining: Vec<&Arc<dyn Indicator>> = registered.iter().collect();
+        while !remaining.is_empty() {
+            let (mut wave, blocked): (Vec<_>, Vec<_>) = remaining.into_iter().partition(|i| {
+                self.dependencies_of(i.name())
+                    .all(|d| !names.contains(d) || indicators.contains_key(d))
+            });
+            remaining = blocked;
+
+            if wave.is_empty() {
+                // The remaining indicators depend on each other, evaluate
+                // them without ordering
+                log::warn!("dependency cycle between {} indicators", self.name);
+                wave = std::mem::take(&mut remaining);
+            }
+
+            // Stable sort, indicators with the same priority keep their
+            // registration order
+            wave.sort_by_key(|i| Reverse(self.priorities.get(i.name()).copied().unwrap_or(0)));
+
+            // Indicators start in priority order, but completed ones free
+            // their slot right away instead of waiting for slower ones
+            // started before them
+            let limit = self.concurrency_limit.unwrap_or(wave.len()).max(1);
+            let evaluations: Vec<_> = wave
+                .iter()
+                .map(|i| async {
+                    let result = self.evaluate_until(i, &indicators, deadline).await;
+                    (i.name().to_string(), result)
+                })
+                .collect();
+            let results: Vec<(String, IndicatorResult)> = futures::stream::iter(evaluations)
+                .buffer_unordered(limit)
+                .collect()
+                .await;
+
+            indicators.extend(results);
+        }
+
+        if let Some(grace) = &self.grace {
+            grace.apply(&mut indicators);
+        }
+
+        self.notify(&indicators, evaluation);
+
+        HealthResponse::new(indicators)
+    }
+
+    fn dependencies_of<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a str> {
+        self.dependencies
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    async fn evaluate_until(
+        &self,
+        indicator: &Arc<dyn Indicator>,
+        evaluated: &BTreeMap<String, IndicatorResult>,
+        deadline: Option<tokio::time::Instant>,
+    ) -> IndicatorResult {
+        let Some(deadline) = deadline else {
+            return self.evaluate(indicator, evaluated).await;
+        };
+
+        let exceeded = || {
+            IndicatorResult::down(format!(
+                "did not complete within the {:?} deadline of the {} group",
+                self.deadline.unwrap_or_default(),
+                self.name
+            ))
+        };
+
+        // Do not start queued indicators once the deadline has passed
+        if tokio::time::Instant::now() >= deadline {
+            return exceeded();
+        }
+
+        tokio::time::timeout_at(deadline, self.evaluate(indicator, evaluated))
+            .await
+            .unwrap_or_else(|_| exceeded())
+    }
+
+    async fn evaluate(
+        &self,
+        indicator: &Arc<dyn Indicator>,
+        evaluated: &BTreeMap<String, IndicatorResult>,
+    ) -> IndicatorResult {
+        if let Some(o) = self
+            .overrides
+            .as_ref()
+            .and_then(|o| o.get(indicator.name()))
+        {
+            return IndicatorResult::from(&o);
+        }
+
+        // Indicators skipped because of their own dependencies are unknown,
+        // skip their dependents as well
+        for dependency in self.dependencies_of(indicator.name()) {
+            let state = match evaluated.get(dependency).map(|r| r.status()) {
+                Some(status) if status.is_failure() => "down",
+                Some(IndicatorStatus::Unknown) => "unknown",
+                _ => continue,
+            };
+            return IndicatorResult::new(IndicatorStatus::Unknown)
+                .with_message(format!("dependency {} is {}", dependency, state));
+        }
+
+        indicator.health().await
+    }
+
+    /// Notifies listeners of status changes. Concurrent evaluations may
+    /// complete out of order, results older than the stored status are
+    /// ignored.
+    fn notify(&self, results: &BTreeMap<String, IndicatorResult>, evaluation: u64) {
+        let mut changes = vec![];
+        {
+            let mut statuses = self.statuses.lock().unwrap_or_else(|e| e.into_inner());
+            // Forget indicators removed from the registry
+            statuses
+                .retain(|name, (observed, _)| *observed > evaluation || results.contains_key(name));
+
+            for (name, result) in results {
+                let old = match statuses.get(name) {
+                    Some((observed, _)) if *observed > evaluation => continue,
+                    Some((_, status)) => Some(*status),
+                    None => None,
+                };
+
+                statuses.insert(name.clone(), (evaluation, result.status()));
+                if old != Some(result.status()) {
+                    changes.push((name, old, result));
+                }
+            }
+        }
+
+        for (name, old, result) in changes {
+            let transition = Transition {
+                group: &self.name,
+                indicator: name,
+                old,
+                new: result.status(),
+                message: result.message(),
+            };
+
+            for listener in &self.listeners {
+                listener.on_transition(&transition);
+            }
+        }
+    }
+}
+
+impl Grace {
+    /// Reports failing indicators as starting while the grace period is
+    /// active.
+    fn apply(&self, results: &mut BTreeMap<String, IndicatorResult>) {
+        if self.ended.load(Ordering::Relaxed) || Instant::now() >= self.until {
+            return;
+        }
+
+        {
+            let mut succeeded = self.succeeded.lock().unwrap_or_else(|e| e.into_inner());
+            succeeded.extend(
+                results
+                    .iter()
+                    .filter(|(_, r)| {
+                        !r.status().is_failure() && r.status() != IndicatorStatus::Unknown
+                    })
+                    .map(|(name, _)| name.clone()),
+            );
+
+            if results.keys().all(|name| succeeded.contains(name)) {
+                self.ended.store(true, Ordering::Relaxed);
+                return;
+            }
+        }
+
+        for result in results.values_mut() {
+            if result.status().is_failure() && !result.is_overridden() {
+                let cause = result.message().map(str::to_string);
+                let mut starting = IndicatorResult::new(IndicatorStatus::Unknown)
+                    .with_message("starting".to_string());
+                if let Some(cause) = cause {
+                    starting = starting.with_detail("cause", cause);
+                }
+                *result = starting;
+            }
+        }
+    }
+}
+
+/// Request guard deciding whether the request may see the indicators of the
+/// health group managed as `T`.
+pub(crate) struct DetailsVisible<T> {
+    pub(crate) visible: bool,
+    check: PhantomData<fn() -> T>,
+}
+
+#[async_trait::async_trait]
+impl<'r, T: Check + Send + Sync + 'static> FromRequest<'r> for DetailsVisible<T> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(check) = req.rocket().state::<T>() else {
+            return Outcome::Error((Status::InternalServerError, ()));
+        };
+
+        Outcome::Success(DetailsVisible {
+            visible: check.group().details_visible(req).await,
+            check: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+
+    use crate::health::{
+        indicator_fn, HealthResponse, Indicator, IndicatorResult, IndicatorStatus, Listener,
+        Transition,
+    };
+
+    use super::Group;
+
+    type Log = Arc<Mutex<Vec<&'static str>>>;
+
+    type Transitions = Arc<Mutex<Vec<(String, Option<IndicatorStatus>, IndicatorStatus)>>>;
+
+    struct Recorder(Transitions);
+
+    impl Listener for Recorder {
+        fn on_transition(&self, transition: &Transition) {
+            self.0.lock().unwrap().push((
+                transition.indicator.to_string(),
+                transition.old,
+                transition.new,
+            ));
+        }
+    }
+
+    fn recorded(group: &mut Group) -> Transitions {
+        let transitions = Transitions::default();
+        group.add_listener(Box::new(Recorder(transitions.clone())));
+        transitions
+    }
+
+    /// Returns an indicator reporting the given status after the given
+    /// delay, logging its name when the evaluation starts.
+    fn indicator(
+        name: &'static str,
+        status: IndicatorStatus,
+        delay: Duration,
+        log: &Log,
+    ) -> Box<dyn Indicator> {
+        let log = log.clone();
+        indicator_fn(name, move || {
+            log.lock().unwrap().push(name);
+            async move {
+                tokio::time::sleep(delay).await;
+                IndicatorResult::new(status)
+            }
+        })
+    }
+
+    fn statuses(response: &HealthResponse) -> Vec<(&str, IndicatorStatus, Option<&str>)> {
+        response
+            .indicators
+            .iter()
+            .map(|(name, result)| (name.as_str(), result.status(), result.message()))
+            .collect()
+    }
+
+    fn results(status: IndicatorStatus) -> BTreeMap<String, IndicatorResult> {
+        BTreeMap::from([("db".to_string(), IndicatorResult::new(status))])
+    }
+
+    #[tokio::test]
+    async fn notifies_initial_status_and_changes() {
+        let mut group = Group::new(
+            "test".to_string(),
+            vec![indicator_fn("db", || async { Ok(()) })],
+        )
+        .unwrap();
+        let transitions = recorded(&mut group);
+
+        group.check().await;
+        group.check().await;
+
+        assert_eq!(
+            *transitions.lock().unwrap(),
+            vec![("db".to_string(), None, IndicatorStatus::Up)]
+        );
+    }
+
+    #[test]
+    fn ignores_results_of_older_evaluations() {
+        let mut group = Group::new("test".to_string(), vec![]).unwrap();
+        let transitions = recorded(&mut group);
+
+        group.notify(&results(IndicatorStatus::Down), 0);
+        group.notify(&results(IndicatorStatus::Up), 2);
+        // Started before the previous evaluation, but completed after it
+        group.notify(&results(IndicatorStatus::Down), 1);
+        group.notify(&BTreeMap::new(), 1);
+        // Not forgotten by the older evaluation
+        group.notify(&results(IndicatorStatus::Up), 3);
+
+        assert_eq!(
+            *transitions.lock().unwrap(),
+            vec![
+                ("db".to_string(), None, IndicatorStatus::Down),
+                (
+                    "db".to_string(),
+                    Some(IndicatorStatus::Down),
+                    IndicatorStatus::Up
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn evaluates_dependencies_first() {
+        let log = Log::default();
+        let mut group = Group::new(
+            "test".to_string(),
+            vec![
+                indicator("api", IndicatorStatus::Up, Duration::ZERO, &log),
+                indicator("cache", IndicatorStatus::Up, Duration::ZERO, &log),
+                indicator("db", IndicatorStatus::Up, Duration::ZERO, &log),
+            ],
+        )
+        .unwrap();
+        group.add_dependency("api", "db");
+        group.add_dependency("api", "cache");
+        group.add_dependency("cache", "db");
+        group.add_dependency("db", "missing");
+
+        let response = group.check().await;
+
+        assert_eq!(*log.lock().unwrap(), vec!["db", "cache", "api"]);
+        assert_eq!(response.status, IndicatorStatus::Up);
+    }
+
+    #[tokio::test]
+    async fn skips_dependents_of_failed_indicators() {
+        let log = Log::default();
+        let mut group = Group::new(
+            "test".to_string(),
+            vec![
+                indicator("db", IndicatorStatus::Down, Duration::ZERO, &log),
+                indicator("api", IndicatorStatus::Up, Duration::ZERO, &log),
+                indicator("frontend", IndicatorStatus::Up, Duration::ZERO, &log),
+            ],
+        )
+        .unwrap();
+        group.add_dependency("api", "db");
+        group.add_dependency("frontend", "api");
+
+        let response = group.check().await;
+
+        assert_eq!(*log.lock().unwrap(), vec!["db"]);
+        assert_eq!(
+            statuses(&response),
+            vec![
+                (
+                    "api",
+                    IndicatorStatus::Unknown,
+                    Some("dependency db is down")
+                ),
+                ("db", IndicatorStatus::Down, None),
+                (
+                    "frontend",
+                    IndicatorStatus::Unknown,
+                    Some("dependency api is unknown")
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn evaluates_dependency_cycles() {
+        let log = Log::default();
+        let mut group = Group::new(
+            "test".to_string(),
+            vec![
+                indicator("a", IndicatorStatus::Up, Duration::ZERO, &log),
+                indicator("b", IndicatorStatus::Up, Duration::ZERO, &log),
+            ],
+        )
+        .unwrap();
+        group.add_dependency("a", "b");
+        group.add_dependency("b", "a");
+
+        let response = group.check().await;
+
+        assert_eq!(*log.lock().unwrap(), vec!["a", "b"]);
+        assert_eq!(response.status, IndicatorStatus::Up);
+    }
+
+    /// Returns an indicator reporting the current status of the given
+    /// handle, with the message `refused` when failing.
+    fn switchable(name: &'static str, status: &Arc<Mutex<IndicatorStatus>>) -> Box<dyn Indicator> {
+        let status = status.clone();
+        indicator_fn(name, move || {
+            let status = *status.lock().unwrap();
+            async move {
+                match status {
+                    IndicatorStatus::Down => IndicatorResult::down("refused".to_string()),
+                    status => IndicatorResult::new(status),
+                }
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn reports_failures_as_starting_during_grace_period() {
+        let db = Arc::new(Mutex::new(IndicatorStatus::Down));
+        let mut group = Group::new(
+            "test".to_string(),
+            vec![
+                switchable("db", &db),
+                indicator_fn("api", || async { Ok(()) }),
+            ],
+        )
+        .unwrap();
+        group.set_grace_period(Duration::from_secs(60));
+
+        let response = group.check().await;
+        assert!(!response.status.is_failure());
+        assert_eq!(
+            statuses(&response),
+            vec![
+                ("api", IndicatorStatus::Up, None),
+                ("db", IndicatorStatus::Unknown, Some("starting")),
+            ]
+        );
+        assert_eq!(response.indicators["db"].details["cause"], "refused");
+
+        // Every indicator succeeded once, the grace period ends early
+        *db.lock().unwrap() = IndicatorStatus::Up;
+        assert_eq!(group.check().await.status, IndicatorStatus::Up);
+        *db.lock().unwrap() = IndicatorStatus::Down;
+        assert_eq!(group.check().await.status, IndicatorStatus::Down);
+    }
+
+    #[tokio::test]
+    async fn reports_failures_after_grace_period() {
+        let db = Arc::new(Mutex::new(IndicatorStatus::Down));
+        let mut group = Group::new("test".to_string(), vec![switchable("db", &db)]).unwrap();
+        group.set_grace_period(Duration::ZERO);
+
+        let response = group.check().await;
+        assert_eq!(
+            statuses(&response),
+            vec![("db", IndicatorStatus::Down, Some("refused"))]
+        );
+    }
+
+    #[tokio::test]
+    async fn limits_concurrency() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let max = Arc::new(AtomicUsize::new(0));
+        let indicators = ["a", "b", "c", "d", "e"].map(|name| {
+            let (running, max) = (running.clone(), max.clone());
+            indicator_fn(name, move || {
+                let (running, max) = (running.clone(), max.clone());
+                async move {
+                    max.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok::<(), String>(())
+                }
+            })
+        });
+        let mut group = Group::new("test".to_string(), indicators.into()).unwrap();
+        group.set_concurrency_limit(2);
+
+        assert_eq!(group.check().await.status, IndicatorStatus::Up);
+        assert_eq!(max.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn evaluates_by_priority() {
+        let log = Log::default();
+        let mut group = Group::new(
+            "test".to_string(),
+            ["a", "b", "c", "d"]
+                .map(|name| indicator(name, IndicatorStatus::Up, Duration::ZERO, &log))
+                .into(),
+        )
+        .unwrap();
+        group.set_concurrency_limit(1);
+        group.set_priority("c", 10);
+        group.set_priority("d", 10);
+        group.set_priority("a", -1);
+
+        group.check().await;
+
+        assert_eq!(*log.lock().unwrap(), vec!["c", "d", "b", "a"]);
+    }
+
+    #[tokio::test]
+    async fn reports_indicators_exceeding_deadline() {
+        let log = Log::default();
+        let mut group = Group::new(
+            "test".to_string(),
+            vec![
+                indicator("fast", IndicatorStatus::Up, Duration::ZERO, &log),
+                indicator("slow", IndicatorStatus::Up, Duration::from_secs(5), &log),
+                indicator("queued", IndicatorStatus::Up, Duration::ZERO, &log),
+            ],
+        )
+        .unwrap();
+        group.set_concurrency_limit(1);
+        group.set_priority("queued", -1);
+        group.set_deadline(Duration::from_millis(50));
+
+        let response = group.check().await;
+
+        let message = "did not complete within the 50ms deadline of the test group";
+        assert_eq!(
+            statuses(&response),
+            vec![
+                ("fast", IndicatorStatus::Up, None),
+                ("queued", IndicatorStatus::Down, Some(message)),
+                ("slow", IndicatorStatus::Down, Some(message)),
+            ]
+        );
+        assert_eq!(*log.lock().unwrap(), vec!["fast", "slow"]);
+    }
+
+    #[tokio::test]
+    async fn starts_queued_indicators_while_slow_ones_run() {
+        let log = Log::default();
+        let mut group = Group::new(
+            "test".to_string(),
+            vec![
+                indicator(
+                    "a_slow",
+                    IndicatorStatus::Up,
+                    Duration::from_millis(300),
+                    &log,
+                ),
+                indicator("b", IndicatorStatus::Up, Duration::from_millis(100), &log),
+                indicator("c", IndicatorStatus::Up, Duration::from_millis(100), &log),
+                indicator("d", IndicatorStatus::Up, Duration::from_millis(100), &log),
+                indicator("e", IndicatorStatus::Up, Duration::from_millis(100), &log),
+            ],
+        )
+        .unwrap();
+        // The fast indicators take 400ms on the free slot, but would wait
+        // for the slow one to be collected first if slots were freed in
+        // order
+        group.set_concurrency_limit(2);
+        group.set_deadline(Duration::from_millis(450));
+
+        let response = group.check().await;
+
+        assert_eq!(
+            statuses(&response),
+            ["a_slow", "b", "c", "d", "e"].map(|name| (name, IndicatorStatus::Up, None))
+        );
+        assert_eq!(*log.lock().unwrap(), vec!["a_slow", "b", "c", "d", "e"]);
+    }
+}
@@ -0,0 +1,131 @@
+use std::{
+    sync::{atomic::Ordering, Arc},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+use crate::health::{self, IndicatorResult};
+
+use super::{Heartbeat, HeartbeatIndicator};
+
+impl Heartbeat {
+    /// Creates a new heartbeat. Until the first beat, the time since the
+    /// last beat is measured from the creation of the heartbeat.
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            last_beat_micros: Arc::new(0.into()),
+        }
+    }
+
+    /// Records that the worker is making progress.
+    pub fn beat(&self) {
+        self.last_beat_micros
+            .store(self.started.elapsed().as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Returns the time since the last beat.
+    pub fn elapsed(&self) -> Duration {
+        let last_beat = Duration::from_micros(self.last_beat_micros.load(Ordering::Relaxed));
+        self.started.elapsed().saturating_sub(last_beat)
+    }
+}
+
+impl Default for Heartbeat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeartbeatIndicator {
+    /// Creates a new heartbeat indicator reporting down when the heartbeat
+    /// has not beaten within the given interval.
+    pub fn new(name: String, heartbeat: Heartbeat, interval: Duration) -> Self {
+        Self {
+            name,
+            heartbeat,
+            interval,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl health::Indicator for HeartbeatIndicator {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.health().await.into()
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        let elapsed = self.heartbeat.elapsed();
+
+        let result = if elapsed > self.interval {
+            IndicatorResult::down(format!(
+                "no heartbeat for {:?}, expected one every {:?}",
+                elapsed, self.interval
+            ))
+        } else {
+            IndicatorResult::up()
+        };
+
+        result.with_detail("since_last_beat_ms", elapsed.as_millis() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::health::{Indicator, IndicatorStatus};
+
+    use super::{Heartbeat, HeartbeatIndicator};
+
+    fn indicator(heartbeat: &Heartbeat) -> HeartbeatIndicator {
+        HeartbeatIndicator::new(
+            "worker".to_string(),
+            heartbeat.clone(),
+            Duration::from_secs(10),
+        )
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reports_stale_heartbeat() {
+        let heartbeat = Heartbeat::new();
+        let indicator = indicator(&heartbeat);
+
+        heartbeat.beat();
+        tokio::time::advance(Duration::from_secs(10)).await;
+        let result = indicator.health().await;
+        assert_eq!(result.status, IndicatorStatus::Up);
+        assert_eq!(result.details["since_last_beat_ms"], 10_000);
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        let result = indicator.health().await;
+        assert_eq!(result.status, IndicatorStatus::Down);
+        assert_eq!(
+            result.message.as_deref(),
+            Some("no heartbeat for 11s, expected one every 10s")
+        );
+
+        heartbeat.beat();
+        let result = indicator.health().await;
+        assert_eq!(result.status, IndicatorStatus::Up);
+        assert_eq!(result.details["since_last_beat_ms"], 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn measures_from_creation_until_first_beat() {
+        let heartbeat = Heartbeat::new();
+        let indicator = indicator(&heartbeat);
+
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert_eq!(indicator.health().await.status, IndicatorStatus::Up);
+
+        tokio::time::advance(Duration::from_secs(6)).await;
+        assert_eq!(indicator.health().await.status, IndicatorStatus::Down);
+    }
+}
@@ -0,0 +1,262 @@
+use std::time::{Duration, Instant};
+
+use reqwest::{Client, Method};
+use rocket::serde::json::Value;
+
+use crate::health::{self, IndicatorResult};
+
+use super::Http;
+
+impl Http {
+    /// Creates a new HTTP indicator issuing GET requests to the given url
+    /// and expecting a 2xx response.
+    pub fn new(name: String, url: String, timeout: Duration) -> Self {
+        Self {
+            name,
+            url,
+            timeout,
+            method: Method::GET,
+            expected_statuses: vec![],
+            json_path: None,
+            client: Client::new(),
+        }
+    }
+
+    /// Sets the request method, e.g. HEAD.
+    pub fn with_method(mut self, method: Method) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Sets the expected response status codes instead of any 2xx.
+    pub fn with_expected_statuses(mut self, statuses: Vec<u16>) -> Self {
+        self.expected_statuses = statuses;
+        self
+    }
+
+    /// Expects the JSON response body to contain the given value at the
+    /// given dot separated path, e.g. `checks.database.0.status`. A leading
+    /// `$.` is ignored.
+    pub fn with_json_path(mut self, path: String, expected: Value) -> Self {
+        self.json_path = Some((path, expected));
+        self
+    }
+
+    /// Sets the client used for requests, e.g. to configure TLS.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    fn is_expected(&self, status: u16) -> bool {
+        if self.expected_statuses.is_empty() {
+            (200..300).contains(&status)
+        } else {
+            self.expected_statuses.contains(&status)
+        }
+    }
+
+    async fn request(&self) -> Result<u16, String> {
+        let response = self
+            .client
+            .request(self.method.clone(), &self.url)
+            .timeout(self.timeout)
+            .send()
+            .await
+            .map_err(|e| format!("request to {} failed: {}", self.url, e))?;
+
+        let status = response.status().as_u16();
+        if !self.is_expected(status) {
+            return Err(format!(
+                "{} returned unexpected status {}",
+                self.url, status
+            ));
+        }
+
+        let Some((path, expected)) = &self.json_path else {
+            return Ok(status);
+        };
+
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| format!("could not read response of {}: {}", self.url, e))?;
+        let body: Value = rocket::serde::json::serde_json::from_slice(&body)
+            .map_err(|e| format!("{} returned invalid json: {}", self.url, e))?;
+
+        match lookup(&body, path) {
+            Some(value) if value == expected => Ok(status),
+            Some(value) => Err(format!(
+                "{} returned {} at {}, expected {}",
+                self.url, value, path, expected
+            )),
+            None => Err(format!("{} returned no value at {}", self.url, path)),
+        }
+    }
+}
+
+/// Looks up a dot separated path in a JSON value, using numeric segments as
+/// array indices.
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.strip_prefix("$.").unwrap_or(path);
+
+    path.split('.')
+        .filter(|s| !s.is_empty())
+        .try_fold(value, |value, segment| match value {
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => value.get(segment),
+        })
+}
+
+#[async_trait::async_trait]
+impl health::Indicator for Http {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.health().await.into()
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        let start = Instant::now();
+        let result = self.request().await;
+        let response_time = start.elapsed();
+
+        match result {
+            Ok(status) => IndicatorResult::up().with_detail("status_code", status),
+            Err(e) => IndicatorResult::down(e),
+        }
+        .with_detail("url", self.url.clone())
+        .with_detail("response_time_ms", response_time.as_secs_f64() * 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rocket::{
+        config::LogLevel,
+        fairing::AdHoc,
+        get,
+        http::Status,
+        routes,
+        serde::json::{json, Value},
+        Config,
+    };
+
+    use crate::health::{Indicator, IndicatorResult, IndicatorStatus};
+
+    use super::{lookup, Http};
+
+    #[get("/status")]
+    fn status() -> Value {
+        json!({"checks": {"database": [{"status": "UP"}]}})
+    }
+
+    #[get("/teapot")]
+    fn teapot() -> Status {
+        Status::ImATeapot
+    }
+
+    #[get("/text")]
+    fn text() -> &'static str {
+        "UP"
+    }
+
+    #[get("/slow")]
+    async fn slow() -> &'static str {
+        rocket::tokio::time::sleep(Duration::from_secs(5)).await;
+        "UP"
+    }
+
+    /// Launches a server on a free port, returning its base url.
+    async fn serve() -> String {
+        let (tx, rx) = rocket::tokio::sync::oneshot::channel();
+        let config = Config {
+            port: 0,
+            log_level: LogLevel::Off,
+            ..Config::debug_default()
+        };
+        let rocket = rocket::custom(config)
+            .mount("/", routes![status, teapot, text, slow])
+            .attach(AdHoc::on_liftoff("Port", |rocket| {
+                Box::pin(async move {
+                    let _ = tx.send(rocket.config().port);
+                })
+            }));
+        rocket::tokio::spawn(rocket.launch());
+        format!("http://127.0.0.1:{}", rx.await.unwrap())
+    }
+
+    fn http(url: String) -> Http {
+        Http::new("http".to_string(), url, Duration::from_secs(5))
+    }
+
+    fn message(result: IndicatorResult) -> String {
+        assert_eq!(result.status, IndicatorStatus::Down);
+        result.message.unwrap()
+    }
+
+    #[tokio::test]
+    async fn verifies_status() {
+        let base = serve().await;
+
+        let result = http(format!("{}/status", base)).health().await;
+        assert_eq!(result.status, IndicatorStatus::Up);
+        assert_eq!(result.details["status_code"], 200);
+
+        let teapot = http(format!("{}/teapot", base));
+        assert!(message(teapot.health().await).ends_with("returned unexpected status 418"));
+
+        let teapot = http(format!("{}/teapot", base)).with_expected_statuses(vec![418]);
+        assert_eq!(teapot.health().await.status, IndicatorStatus::Up);
+    }
+
+    #[tokio::test]
+    async fn verifies_json_path() {
+        let base = serve().await;
+        let http = |path: &str, expected: &str| {
+            http(format!("{}{}", base, path))
+                .with_json_path("$.checks.database.0.status".to_string(), json!(expected))
+        };
+
+        assert_eq!(
+            http("/status", "UP").health().await.status,
+            IndicatorStatus::Up
+        );
+        assert!(message(http("/status", "DOWN").health().await)
+            .ends_with(r#"returned "UP" at $.checks.database.0.status, expected "DOWN""#));
+        assert!(message(http("/text", "UP").health().await).contains("returned invalid json"));
+    }
+
+    #[tokio::test]
+    async fn times_out() {
+        let base = serve().await;
+        let start = std::time::Instant::now();
+        let result = Http::new(
+            "http".to_string(),
+            format!("{}/slow", base),
+            Duration::from_millis(100),
+        )
+        .health()
+        .await;
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(message(result).starts_with(&format!("request to {}/slow failed", base)));
+    }
+
+    #[test]
+    fn looks_up_paths() {
+        let value = json!({"a": {"b": [{"c": 1}, {"c": 2}]}, "d": "e"});
+
+        assert_eq!(lookup(&value, "d"), Some(&json!("e")));
+        assert_eq!(lookup(&value, "$.d"), Some(&json!("e")));
+        assert_eq!(lookup(&value, "a.b.1.c"), Some(&json!(2)));
+        assert_eq!(lookup(&value, "$.a.b.0"), Some(&json!({"c": 1})));
+        assert_eq!(lookup(&value, "$."), Some(&value));
+        assert_eq!(lookup(&value, "a.b.2.c"), None);
+        assert_eq!(lookup(&value, "a.b.c"), None);
+        assert_eq!(lookup(&value, "d.e"), None);
+    }
+}
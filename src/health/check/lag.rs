@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use crate::{
+    health::{self, IndicatorResult, IndicatorStatus},
+    lag::LagMonitor,
+};
+
+use super::EventLoopLag;
+
+impl EventLoopLag {
+    /// Creates a new event loop lag indicator reporting degraded when the
+    /// lag exceeds the degraded threshold and down when it exceeds the down
+    /// threshold.
+    pub fn new(name: String, monitor: LagMonitor, degraded: Duration, down: Duration) -> Self {
+        Self {
+            name,
+            monitor,
+            degraded,
+            down,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl health::Indicator for EventLoopLag {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.health().await.into()
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        let lag = self.monitor.lag();
+
+        let result = if lag > self.down {
+            IndicatorResult::down(format!(
+                "event loop lag of {:?} exceeds {:?}",
+                lag, self.down
+            ))
+        } else if lag > self.degraded {
+            IndicatorResult::new(IndicatorStatus::Degraded).with_message(format!(
+                "event loop lag of {:?} exceeds {:?}",
+                lag, self.degraded
+            ))
+        } else {
+            IndicatorResult::up()
+        };
+
+        result.with_detail("lag_ms", lag.as_secs_f64() * 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        health::{check::EventLoopLag, Indicator, IndicatorStatus},
+        lag::LagMonitor,
+    };
+
+    // Blocking the current thread runtime of the test delays the monitor by
+    // about the blocked duration
+    #[tokio::test]
+    async fn maps_lag_to_status() {
+        let indicator = EventLoopLag::new(
+            "event_loop".to_string(),
+            LagMonitor::start(Duration::from_millis(10)),
+            Duration::from_millis(50),
+            Duration::from_millis(500),
+        );
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let result = indicator.health().await;
+        assert_eq!(result.status, IndicatorStatus::Up);
+        assert!(result.details.contains_key("lag_ms"));
+
+        std::thread::sleep(Duration::from_millis(150));
+        let result = indicator.health().await;
+        assert_eq!(result.status, IndicatorStatus::Degraded);
+        assert!(result.message.unwrap().starts_with("event loop lag of"));
+
+        std::thread::sleep(Duration::from_millis(600));
+        let result = indicator.health().await;
+        assert_eq!(result.status, IndicatorStatus::Down);
+        assert!(result.message.unwrap().ends_with("exceeds 500ms"));
+    }
+}
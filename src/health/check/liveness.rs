@@ -1,34 +1,195 @@
-use std::collections::HashMap;
+use std::sync::Arc;
 
-use rocket::{get, routes, serde::json::Json, Build, Rocket, State};
+use rocket::{get, routes, Build, Rocket, State};
 
 use crate::{
-    actuator::RocketConfigurerer,
-    health,
-    health::{Check, IndicatorResult},
+    actuator::{join_path, RocketConfigurerer},
+    auth::{AccessPolicy, Authorized, Denied, Protected},
+    health::{self, Check, HealthResponse},
 };
 
-#[get("/health/liveness")]
+use super::{group::DetailsVisible, DuplicateIndicator, Group, Liveness, Registry};
+
+#[get("/")]
 async fn liveness(
+    auth: Result<Authorized<Liveness>, Denied>,
+    details: DetailsVisible<Liveness>,
     liveness_check: &State<health::check::Liveness>,
-) -> rocket::response::status::Custom<Json<HashMap<&str, IndicatorResult>>> {
-    liveness_check.inner().check().await
+) -> Result<HealthResponse, Denied> {
+    auth?;
+
+    let response = liveness_check.inner().check().await;
+    if details.visible {
+        Ok(response)
+    } else {
+        Ok(response.without_details())
+    }
 }
 
 impl health::check::Liveness {
-    pub fn new(indicators: Vec<Box<dyn health::Indicator>>) -> Self {
-        Self { indicators }
+    /// Creates a new liveness probe. Fails if several indicators have the same
+    /// name.
+    pub fn new(indicators: Vec<Box<dyn health::Indicator>>) -> Result<Self, DuplicateIndicator> {
+        Ok(Self {
+            group: Group::new("liveness".to_string(), indicators)?,
+            path: "/health/liveness".to_string(),
+            access_policy: None,
+        })
+    }
+
+    /// Sets the path of the liveness endpoint, relative to the base path of the
+    /// actuator. Defaults to `/health/liveness`.
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path = path.to_string();
+        self
+    }
+
+    /// Requires requests to the liveness endpoint to satisfy the given policy.
+    pub fn with_access_policy(mut self, policy: impl AccessPolicy + 'static) -> Self {
+        self.access_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Sets who sees the liveness indicators. Other callers only get the
+    /// overall status.
+    pub fn with_show_details(mut self, show_details: health::ShowDetails) -> Self {
+        self.group.set_show_details(show_details);
+        self
+    }
+
+    /// Adds a listener notified whenever the status of a liveness indicator
+    /// changes.
+    pub fn with_listener(mut self, listener: Box<dyn health::Listener>) -> Self {
+        self.group.add_listener(listener);
+        self
+    }
+
+    /// Applies the given administrative overrides to liveness indicators.
+    pub fn with_overrides(mut self, overrides: health::overrides::Overrides) -> Self {
+        self.group.set_overrides(overrides);
+        self
+    }
+
+    /// Declares that the liveness indicator with the given name depends on
+    /// another liveness indicator. When the dependency fails, the indicator is
+    /// not evaluated and reported as unknown.
+    pub fn with_dependency(mut self, indicator: &str, dependency: &str) -> Self {
+        self.group.add_dependency(indicator, dependency);
+        self
+    }
+
+    /// Limits the number of liveness indicators evaluated concurrently.
+    /// Further indicators are queued in priority order.
+    pub fn with_concurrency_limit(mut self, limit: usize) -> Self {
+        self.group.set_concurrency_limit(limit);
+        self
+    }
+
+    /// Sets the priority of the liveness indicator with the given name, higher
+    /// priorities are evaluated first when the concurrency limit is reached.
+    pub fn with_priority(mut self, indicator: &str, priority: i32) -> Self {
+        self.group.set_priority(indicator, priority);
+        self
+    }
+
+    /// Sets the maximum duration of the liveness evaluation. Indicators that
+    /// have not completed by the deadline are reported as down.
+    pub fn with_deadline(mut self, deadline: std::time::Duration) -> Self {
+        self.group.set_deadline(deadline);
+        self
+    }
+
+    /// Starts a grace period during which failing liveness indicators are
+    /// reported as unknown and do not fail the liveness probe. The grace period
+    /// ends early once every indicator has succeeded at least once.
+    pub fn with_grace_period(mut self, period: std::time::Duration) -> Self {
+        self.group.set_grace_period(period);
+        self
+    }
+
+    /// Adds the `supervised_tasks` indicator, reporting down when a critical
+    /// task of the given supervisor has stopped. Fails if liveness already
+    /// has an indicator named `supervised_tasks`.
+    #[cfg(feature = "supervisor")]
+    pub fn with_supervised_tasks(
+        self,
+        supervisor: crate::supervisor::TaskSupervisor,
+    ) -> Result<Self, DuplicateIndicator> {
+        self.group.registry().add(Box::new(super::Supervisor::new(
+            "supervised_tasks".to_string(),
+            supervisor,
+        )))?;
+        Ok(self)
+    }
+
+    /// Returns a handle to add or remove liveness indicators, also after the
+    /// server is launched.
+    pub fn registry(&self) -> Registry {
+        self.group.registry().clone()
+    }
+}
+
+impl health::Check for Liveness {
+    fn group(&self) -> &Group {
+        &self.group
+    }
+}
+
+impl Protected for Liveness {
+    fn access_policy(&self) -> Option<&Arc<dyn AccessPolicy>> {
+        self.access_policy.as_ref()
     }
 }
 
-impl health::Check for health::check::Liveness {
-    fn indicators(&self) -> &Vec<Box<dyn health::Indicator>> {
-        &self.indicators
+impl RocketConfigurerer for Liveness {
+    fn configure(self, rocket: Rocket<Build>, base_path: &str) -> Rocket<Build> {
+        let path = join_path(base_path, &self.path);
+        rocket.manage(self).mount(path, routes![liveness])
     }
 }
 
-impl RocketConfigurerer for health::check::Liveness {
-    fn configure(self, rocket: Rocket<Build>) -> Rocket<Build> {
-        rocket.manage(self).mount("/", routes![liveness])
+#[cfg(test)]
+mod tests {
+    use rocket::local::asynchronous::Client;
+
+    use crate::{
+        actuate,
+        health::{check::Liveness, indicator_fn, Indicator},
+    };
+
+    fn up(name: &'static str) -> Box<dyn Indicator> {
+        indicator_fn(name, || async { Ok::<(), String>(()) })
+    }
+
+    #[test]
+    fn rejects_duplicate_indicators() {
+        let err = Liveness::new(vec![up("db"), up("cache"), up("db")])
+            .err()
+            .unwrap();
+        assert_eq!(err.name(), "db");
+
+        let liveness = Liveness::new(vec![up("db")]).unwrap();
+        assert_eq!(liveness.registry().add(up("db")).unwrap_err().name(), "db");
+    }
+
+    #[tokio::test]
+    async fn orders_indicators_by_name() {
+        let liveness = Liveness::new(vec![up("zookeeper"), up("cache"), up("kafka")]).unwrap();
+        let client = Client::untracked(actuate(rocket::build()).with_liveness(liveness).get())
+            .await
+            .unwrap();
+
+        let body = client
+            .get("/health/liveness")
+            .dispatch()
+            .await
+            .into_string()
+            .await
+            .unwrap();
+        let positions: Vec<_> = ["cache", "kafka", "zookeeper"]
+            .iter()
+            .map(|name| body.find(name).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]), "{body}");
     }
 }
@@ -1,35 +1,180 @@
-use std::collections::HashMap;
+use std::sync::Arc;
 
-use rocket::{get, routes, serde::json::Json, Build, Rocket, State};
+use rocket::{get, routes, Build, Rocket, State};
 
 use crate::{
-    actuator::RocketConfigurerer,
-    health::{self, Check, IndicatorResult},
+    actuator::{join_path, RocketConfigurerer},
+    auth::{AccessPolicy, Authorized, Denied, Protected},
+    health::{self, Check, HealthResponse},
 };
 
-use super::Readiness;
+use super::{group::DetailsVisible, DuplicateIndicator, Group, Readiness, Registry};
 
-#[get("/health/readiness")]
+#[get("/")]
 async fn readiness(
+    auth: Result<Authorized<Readiness>, Denied>,
+    details: DetailsVisible<Readiness>,
     readiness_check: &State<health::check::Readiness>,
-) -> rocket::response::status::Custom<Json<HashMap<&str, IndicatorResult>>> {
-    readiness_check.inner().check().await
+) -> Result<HealthResponse, Denied> {
+    auth?;
+
+    let response = readiness_check.inner().check().await;
+    if details.visible {
+        Ok(response)
+    } else {
+        Ok(response.without_details())
+    }
 }
 
 impl health::check::Readiness {
-    pub fn new(indicators: Vec<Box<dyn health::Indicator>>) -> Self {
-        Self { indicators }
+    /// Creates a new readiness probe. Fails if several indicators have the same
+    /// name.
+    pub fn new(indicators: Vec<Box<dyn health::Indicator>>) -> Result<Self, DuplicateIndicator> {
+        Ok(Self {
+            group: Group::new("readiness".to_string(), indicators)?,
+            path: "/health/readiness".to_string(),
+            access_policy: None,
+        })
+    }
+
+    /// Sets the path of the readiness endpoint, relative to the base path of the
+    /// actuator. Defaults to `/health/readiness`.
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path = path.to_string();
+        self
+    }
+
+    /// Requires requests to the readiness endpoint to satisfy the given policy.
+    pub fn with_access_policy(mut self, policy: impl AccessPolicy + 'static) -> Self {
+        self.access_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Sets who sees the readiness indicators. Other callers only get the
+    /// overall status.
+    pub fn with_show_details(mut self, show_details: health::ShowDetails) -> Self {
+        self.group.set_show_details(show_details);
+        self
+    }
+
+    /// Adds a listener notified whenever the status of a readiness indicator
+    /// changes.
+    pub fn with_listener(mut self, listener: Box<dyn health::Listener>) -> Self {
+        self.group.add_listener(listener);
+        self
+    }
+
+    /// Applies the given administrative overrides to readiness indicators.
+    pub fn with_overrides(mut self, overrides: health::overrides::Overrides) -> Self {
+        self.group.set_overrides(overrides);
+        self
+    }
+
+    /// Declares that the readiness indicator with the given name depends on
+    /// another readiness indicator. When the dependency fails, the indicator is
+    /// not evaluated and reported as unknown.
+    pub fn with_dependency(mut self, indicator: &str, dependency: &str) -> Self {
+        self.group.add_dependency(indicator, dependency);
+        self
+    }
+
+    /// Limits the number of readiness indicators evaluated concurrently.
+    /// Further indicators are queued in priority order.
+    pub fn with_concurrency_limit(mut self, limit: usize) -> Self {
+        self.group.set_concurrency_limit(limit);
+        self
+    }
+
+    /// Sets the priority of the readiness indicator with the given name, higher
+    /// priorities are evaluated first when the concurrency limit is reached.
+    pub fn with_priority(mut self, indicator: &str, priority: i32) -> Self {
+        self.group.set_priority(indicator, priority);
+        self
+    }
+
+    /// Sets the maximum duration of the readiness evaluation. Indicators that
+    /// have not completed by the deadline are reported as down.
+    pub fn with_deadline(mut self, deadline: std::time::Duration) -> Self {
+        self.group.set_deadline(deadline);
+        self
+    }
+
+    /// Starts a grace period during which failing readiness indicators are
+    /// reported as unknown and do not fail the readiness probe. The grace period
+    /// ends early once every indicator has succeeded at least once.
+    pub fn with_grace_period(mut self, period: std::time::Duration) -> Self {
+        self.group.set_grace_period(period);
+        self
+    }
+
+    /// Returns a handle to add or remove readiness indicators, also after the
+    /// server is launched.
+    pub fn registry(&self) -> Registry {
+        self.group.registry().clone()
     }
 }
 
-impl crate::health::Check for Readiness {
-    fn indicators(&self) -> &Vec<Box<dyn crate::health::Indicator>> {
-        &self.indicators
+impl health::Check for Readiness {
+    fn group(&self) -> &Group {
+        &self.group
+    }
+}
+
+impl Protected for Readiness {
+    fn access_policy(&self) -> Option<&Arc<dyn AccessPolicy>> {
+        self.access_policy.as_ref()
     }
 }
 
 impl RocketConfigurerer for Readiness {
-    fn configure(self, rocket: Rocket<Build>) -> Rocket<Build> {
-        rocket.manage(self).mount("/", routes![readiness])
+    fn configure(self, rocket: Rocket<Build>, base_path: &str) -> Rocket<Build> {
+        let path = join_path(base_path, &self.path);
+        rocket.manage(self).mount(path, routes![readiness])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::local::asynchronous::Client;
+
+    use crate::{
+        actuate,
+        health::{check::Readiness, indicator_fn, Indicator},
+    };
+
+    fn up(name: &'static str) -> Box<dyn Indicator> {
+        indicator_fn(name, || async { Ok::<(), String>(()) })
+    }
+
+    #[test]
+    fn rejects_duplicate_indicators() {
+        let err = Readiness::new(vec![up("db"), up("cache"), up("db")])
+            .err()
+            .unwrap();
+        assert_eq!(err.name(), "db");
+
+        let readiness = Readiness::new(vec![up("db")]).unwrap();
+        assert_eq!(readiness.registry().add(up("db")).unwrap_err().name(), "db");
+    }
+
+    #[tokio::test]
+    async fn orders_indicators_by_name() {
+        let readiness = Readiness::new(vec![up("zookeeper"), up("cache"), up("kafka")]).unwrap();
+        let client = Client::untracked(actuate(rocket::build()).with_readiness(readiness).get())
+            .await
+            .unwrap();
+
+        let body = client
+            .get("/health/readiness")
+            .dispatch()
+            .await
+            .into_string()
+            .await
+            .unwrap();
+        let positions: Vec<_> = ["cache", "kafka", "zookeeper"]
+            .iter()
+            .map(|name| body.find(name).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]), "{body}");
     }
 }
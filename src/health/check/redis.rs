@@ -0,0 +1,216 @@
+use std::time::{Duration, Instant};
+
+use ::redis::aio::ConnectionLike;
+
+use crate::health::{self, IndicatorResult};
+
+use super::Redis;
+
+impl<C: ConnectionLike + Clone + Send + Sync> Redis<C> {
+    /// Creates a new redis health indicator running `PING` using the given
+    /// connection, e.g. a `ConnectionManager`.
+    pub fn new(name: String, connection: C) -> Self {
+        Self {
+            name,
+            connection,
+            expected_role: None,
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// Verifies the replication role of the server, e.g. `master`, using
+    /// `INFO replication`.
+    pub fn with_expected_role(mut self, role: String) -> Self {
+        self.expected_role = Some(role);
+        self
+    }
+
+    /// Sets the maximum duration of the commands. Defaults to 5 seconds.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    async fn evaluate(&self) -> IndicatorResult {
+        let mut connection = self.connection.clone();
+
+        let start = Instant::now();
+        let pong: Result<String, _> = ::redis::cmd("PING").query_async(&mut connection).await;
+        let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let result = match pong {
+            Ok(pong) if pong == "PONG" => IndicatorResult::up(),
+            Ok(pong) => IndicatorResult::down(format!("PING returned {}", pong)),
+            Err(e) => return IndicatorResult::down(format!("PING failed: {}", e)),
+        }
+        .with_detail("latency_ms", latency_ms);
+
+        let Some(expected_role) = &self.expected_role else {
+            return result;
+        };
+
+        match self.role(&mut connection).await {
+            Ok(role) if &role == expected_role => result.with_detail("role", role),
+            Ok(role) => IndicatorResult::down(format!(
+                "server role is {}, expected {}",
+                role, expected_role
+            ))
+            .with_detail("latency_ms", latency_ms)
+            .with_detail("role", role),
+            Err(e) => IndicatorResult::down(e).with_detail("latency_ms", latency_ms),
+        }
+    }
+
+    async fn role(&self, connection: &mut C) -> Result<String, String> {
+        let info: String = ::redis::cmd("INFO")
+            .arg("replication")
+            .query_async(connection)
+            .await
+            .map_err(|e| format!("INFO replication failed: {}", e))?;
+
+        info.lines()
+            .find_map(|line| line.strip_prefix("role:"))
+            .map(|role| role.trim().to_string())
+            .ok_or_else(|| "INFO replication did not report a role".to_string())
+    }
+}
+
+#[async_trait::async_trait]
+impl<C: ConnectionLike + Clone + Send + Sync> health::Indicator for Redis<C> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.health().await.into()
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        tokio::time::timeout(self.timeout, self.evaluate())
+            .await
+            .unwrap_or_else(|_| {
+                IndicatorResult::down(format!("redis timed out after {:?}", self.timeout))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ::redis::{aio::ConnectionLike, Cmd, ErrorKind, Pipeline, RedisError, RedisFuture, Value};
+
+    use crate::health::{Indicator, IndicatorResult, IndicatorStatus};
+
+    use super::Redis;
+
+    /// Connection answering `PING` and `INFO` with canned replies.
+    #[derive(Clone)]
+    struct Stub {
+        pong: &'static str,
+        info: Option<&'static str>,
+        delay: Duration,
+    }
+
+    impl ConnectionLike for Stub {
+        fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+            let command = cmd.get_packed_command();
+            Box::pin(async move {
+                tokio::time::sleep(self.delay).await;
+                if command.ends_with(b"PING\r\n") {
+                    Ok(Value::Status(self.pong.to_string()))
+                } else {
+                    self.info
+                        .map(|info| Value::Data(info.as_bytes().to_vec()))
+                        .ok_or_else(|| RedisError::from((ErrorKind::ResponseError, "unknown")))
+                }
+            })
+        }
+
+        fn req_packed_commands<'a>(
+            &'a mut self,
+            _cmd: &'a Pipeline,
+            _offset: usize,
+            _count: usize,
+        ) -> RedisFuture<'a, Vec<Value>> {
+            Box::pin(async {
+                Err(RedisError::from((
+                    ErrorKind::ClientError,
+                    "pipelines not supported",
+                )))
+            })
+        }
+
+        fn get_db(&self) -> i64 {
+            0
+        }
+    }
+
+    fn redis(pong: &'static str, info: Option<&'static str>) -> Redis<Stub> {
+        Redis::new(
+            "redis".to_string(),
+            Stub {
+                pong,
+                info,
+                delay: Duration::ZERO,
+            },
+        )
+    }
+
+    fn message(result: &IndicatorResult) -> &str {
+        assert_eq!(result.status, IndicatorStatus::Down);
+        result.message.as_deref().unwrap()
+    }
+
+    #[tokio::test]
+    async fn pings() {
+        let result = redis("PONG", None).health().await;
+        assert_eq!(result.status, IndicatorStatus::Up);
+        assert!(result.details.contains_key("latency_ms"));
+
+        assert_eq!(
+            message(&redis("LOADING", None).health().await),
+            "PING returned LOADING"
+        );
+    }
+
+    #[tokio::test]
+    async fn verifies_role() {
+        let info = Some("# Replication\r\nrole:slave\r\nconnected_slaves:0\r\n");
+
+        let result = redis("PONG", info)
+            .with_expected_role("slave".to_string())
+            .health()
+            .await;
+        assert_eq!(result.status, IndicatorStatus::Up);
+        assert_eq!(result.details["role"], "slave");
+
+        let result = redis("PONG", info)
+            .with_expected_role("master".to_string())
+            .health()
+            .await;
+        assert_eq!(message(&result), "server role is slave, expected master");
+        assert!(result.details.contains_key("latency_ms"));
+
+        let result = redis("PONG", None)
+            .with_expected_role("master".to_string())
+            .health()
+            .await;
+        assert!(message(&result).starts_with("INFO replication failed"));
+        assert!(result.details.contains_key("latency_ms"));
+    }
+
+    #[tokio::test]
+    async fn times_out() {
+        let stub = Stub {
+            pong: "PONG",
+            info: None,
+            delay: Duration::from_secs(5),
+        };
+        let result = Redis::new("redis".to_string(), stub)
+            .with_timeout(Duration::from_millis(100))
+            .health()
+            .await;
+        assert_eq!(message(&result), "redis timed out after 100ms");
+    }
+}
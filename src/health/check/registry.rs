@@ -0,0 +1,138 @@
+use std::{
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+use crate::health::Indicator;
+
+use super::{DuplicateIndicator, Registry};
+
+impl Registry {
+    /// Creates a registry with the given indicators. Fails if several
+    /// indicators have the same name.
+    pub fn new(indicators: Vec<Box<dyn Indicator>>) -> Result<Self, DuplicateIndicator> {
+        let registry = Self {
+            indicators: Arc::new(RwLock::new(Vec::with_capacity(indicators.len()))),
+        };
+
+        for indicator in indicators {
+            registry.add(indicator)?;
+        }
+
+        Ok(registry)
+    }
+
+    /// Registers an indicator. Fails if an indicator with the same name is
+    /// already registered.
+    pub fn add(&self, indicator: Box<dyn Indicator>) -> Result<(), DuplicateIndicator> {
+        let mut indicators = self.indicators.write().unwrap_or_else(|e| e.into_inner());
+
+        if indicators.iter().any(|i| i.name() == indicator.name()) {
+            return Err(DuplicateIndicator::new(indicator.name().to_string()));
+        }
+
+        indicators.push(Arc::from(indicator));
+        Ok(())
+    }
+
+    /// Unregisters the indicator with the given name, returning it if it
+    /// was registered.
+    pub fn remove(&self, name: &str) -> Option<Arc<dyn Indicator>> {
+        let mut indicators = self.indicators.write().unwrap_or_else(|e| e.into_inner());
+
+        let position = indicators.iter().position(|i| i.name() == name)?;
+        Some(indicators.remove(position))
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.indicators
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .any(|i| i.name() == name)
+    }
+
+    /// Returns the names of the registered indicators in registration order.
+    pub fn names(&self) -> Vec<String> {
+        self.indicators
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|i| i.name().to_string())
+            .collect()
+    }
+
+    /// Returns the currently registered indicators. The lock is released
+    /// before returning so the indicators can be evaluated without blocking
+    /// registrations.
+    pub fn indicators(&self) -> Vec<Arc<dyn Indicator>> {
+        self.indicators
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl DuplicateIndicator {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for DuplicateIndicator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "indicator {} is already registered", self.name)
+    }
+}
+
+impl std::error::Error for DuplicateIndicator {}
+
+#[cfg(test)]
+mod tests {
+    use crate::health::{check::Group, indicator_fn, IndicatorStatus};
+
+    use super::Registry;
+
+    fn up(name: &'static str) -> Box<dyn crate::health::Indicator> {
+        indicator_fn(name, || async { Ok::<(), String>(()) })
+    }
+
+    #[test]
+    fn adds_and_removes_indicators() {
+        let registry = Registry::new(vec![up("db")]).unwrap();
+        registry.add(up("cache")).unwrap();
+        assert!(registry.contains("cache"));
+        assert_eq!(registry.names(), ["db", "cache"]);
+
+        let err = registry.add(up("db")).unwrap_err();
+        assert_eq!(err.name(), "db");
+        assert_eq!(registry.names(), ["db", "cache"]);
+
+        assert_eq!(registry.remove("db").unwrap().name(), "db");
+        assert!(registry.remove("db").is_none());
+        assert!(!registry.contains("db"));
+        assert_eq!(registry.names(), ["cache"]);
+    }
+
+    #[tokio::test]
+    async fn checks_indicators_added_at_runtime() {
+        let group = Group::new("readiness".to_string(), vec![up("db")]).unwrap();
+        let registry = group.registry().clone();
+
+        registry.add(up("cache")).unwrap();
+        let response = group.check().await;
+        assert_eq!(
+            response.indicators.keys().collect::<Vec<_>>(),
+            ["cache", "db"]
+        );
+        assert_eq!(response.indicators["cache"].status, IndicatorStatus::Up);
+
+        registry.remove("db");
+        let response = group.check().await;
+        assert_eq!(response.indicators.keys().collect::<Vec<_>>(), ["cache"]);
+    }
+}
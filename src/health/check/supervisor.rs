@@ -0,0 +1,121 @@
+use rocket::serde::json::json;
+
+use crate::{
+    health::{self, IndicatorResult},
+    supervisor::{TaskState, TaskSupervisor},
+};
+
+use super::Supervisor;
+
+impl Supervisor {
+    /// Creates a new indicator for the tasks of the given supervisor.
+    pub fn new(name: String, supervisor: TaskSupervisor) -> Self {
+        Self { name, supervisor }
+    }
+}
+
+#[async_trait::async_trait]
+impl health::Indicator for Supervisor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.health().await.into()
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        let tasks = self.supervisor.tasks();
+
+        let failures: Vec<String> = tasks
+            .iter()
+            .filter(|t| t.critical && t.state != TaskState::Running)
+            .map(|t| match &t.state {
+                TaskState::Panicked(message) => format!("{} panicked: {}", t.name, message),
+                state => format!("{} {}", t.name, state.as_str()),
+            })
+            .collect();
+
+        let result = if failures.is_empty() {
+            IndicatorResult::up()
+        } else {
+            IndicatorResult::down(failures.join(", "))
+        };
+
+        tasks.into_iter().fold(result, |result, task| {
+            result.with_detail(
+                &task.name,
+                json!({
+                    "critical": task.critical,
+                    "state": task.state.as_str(),
+                    "restarts": task.restarts,
+                }),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::{http::Status, local::asynchronous::Client};
+
+    use crate::{
+        actuate,
+        health::{check::Liveness, indicator_fn},
+        supervisor::TaskSupervisor,
+    };
+
+    async fn liveness(client: &Client) -> (Status, String) {
+        let response = client.get("/health/liveness").dispatch().await;
+        (response.status(), response.into_string().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn reports_critical_tasks_in_liveness() {
+        let actuator = actuate(rocket::build());
+        let supervisor = actuator.task_supervisor();
+        let liveness_check = Liveness::new(vec![])
+            .unwrap()
+            .with_supervised_tasks(supervisor.clone())
+            .unwrap();
+        let client = Client::untracked(actuator.with_liveness(liveness_check).get())
+            .await
+            .unwrap();
+
+        supervisor.spawn("worker", true, std::future::pending::<()>());
+        let (status, body) = liveness(&client).await;
+        assert_eq!(status, Status::Ok);
+        assert!(body.contains("supervised_tasks"));
+
+        let worker = supervisor.spawn("worker", true, std::future::pending::<()>());
+        worker.abort();
+        tokio::task::yield_now().await;
+        let (status, body) = liveness(&client).await;
+        assert_eq!(status, Status::ServiceUnavailable);
+        assert!(body.contains("worker cancelled"));
+    }
+
+    #[tokio::test]
+    async fn reports_supervised_tasks_only_when_enabled() {
+        let actuator = actuate(rocket::build());
+        let client =
+            Client::untracked(actuator.with_liveness(Liveness::new(vec![]).unwrap()).get())
+                .await
+                .unwrap();
+
+        let (status, body) = liveness(&client).await;
+        assert_eq!(status, Status::Ok);
+        assert!(!body.contains("supervised_tasks"));
+    }
+
+    #[test]
+    fn rejects_conflicting_indicator() {
+        let conflicting = indicator_fn("supervised_tasks", || async { Ok::<(), String>(()) });
+        let err = Liveness::new(vec![conflicting])
+            .unwrap()
+            .with_supervised_tasks(TaskSupervisor::default())
+            .err()
+            .unwrap();
+        assert_eq!(err.name(), "supervised_tasks");
+    }
+}
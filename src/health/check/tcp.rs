@@ -0,0 +1,196 @@
+use std::time::{Duration, Instant};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+use crate::health::{self, IndicatorResult};
+
+use super::Tcp;
+
+impl Tcp {
+    /// Creates a new TCP indicator connecting to the given `host:port`
+    /// address.
+    pub fn new(name: String, address: String, timeout: Duration) -> Self {
+        Self {
+            name,
+            address,
+            timeout,
+            send: None,
+            expect: None,
+        }
+    }
+
+    /// Sends the given bytes after connecting, e.g. `b"PING\r\n"`.
+    pub fn with_send(mut self, send: Vec<u8>) -> Self {
+        self.send = Some(send);
+        self
+    }
+
+    /// Expects the response to start with the given bytes, e.g. `b"220"`
+    /// for an SMTP relay.
+    pub fn with_expected_prefix(mut self, expect: Vec<u8>) -> Self {
+        self.expect = Some(expect);
+        self
+    }
+
+    async fn exchange(&self, stream: &mut TcpStream) -> Result<(), String> {
+        if let Some(send) = &self.send {
+            stream
+                .write_all(send)
+                .await
+                .map_err(|e| format!("could not send to {}: {}", self.address, e))?;
+        }
+
+        let Some(expect) = &self.expect else {
+            return Ok(());
+        };
+
+        let mut response = vec![0; expect.len()];
+        let mut read = 0;
+        while read < expect.len() {
+            let n = stream
+                .read(&mut response[read..])
+                .await
+                .map_err(|e| format!("could not read from {}: {}", self.address, e))?;
+            if n == 0 {
+                break;
+            }
+            read += n;
+        }
+
+        if response[..read] != expect[..] {
+            return Err(format!(
+                "unexpected response from {}: {:?}",
+                self.address,
+                String::from_utf8_lossy(&response[..read])
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl health::Indicator for Tcp {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.health().await.into()
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        let start = Instant::now();
+
+        let result = tokio::time::timeout(self.timeout, async {
+            let mut stream = TcpStream::connect(&self.address)
+                .await
+                .map_err(|e| format!("could not connect to {}: {}", self.address, e))?;
+            let latency = start.elapsed();
+
+            self.exchange(&mut stream).await?;
+            Ok(latency)
+        })
+        .await
+        .unwrap_or_else(|_| {
+            Err(format!(
+                "connection to {} timed out after {:?}",
+                self.address, self.timeout
+            ))
+        });
+
+        match result {
+            Ok(latency) => IndicatorResult::up()
+                .with_detail("connect_latency_ms", latency.as_secs_f64() * 1000.0),
+            Err(e) => IndicatorResult::down(e),
+        }
+        .with_detail("address", self.address.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use crate::health::{Indicator, IndicatorResult, IndicatorStatus};
+
+    use super::Tcp;
+
+    /// Starts a server answering every connection with the given response
+    /// after reading the given number of bytes.
+    async fn serve(request_len: usize, response: &'static [u8]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut request = vec![0; request_len];
+                    stream.read_exact(&mut request).await.unwrap();
+                    stream.write_all(response).await.unwrap();
+                    // Keep the connection open, like a slow server.
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                });
+            }
+        });
+        address
+    }
+
+    fn message(result: IndicatorResult) -> String {
+        assert_eq!(result.status, IndicatorStatus::Down);
+        result.message.unwrap()
+    }
+
+    #[tokio::test]
+    async fn connects() {
+        let address = serve(0, b"").await;
+        let result = Tcp::new("tcp".to_string(), address, Duration::from_secs(5))
+            .health()
+            .await;
+        assert_eq!(result.status, IndicatorStatus::Up);
+        assert!(result.details.contains_key("connect_latency_ms"));
+    }
+
+    #[tokio::test]
+    async fn verifies_response_prefix() {
+        let address = serve(6, b"+PONG\r\n").await;
+        let tcp = |expect: &[u8]| {
+            Tcp::new("tcp".to_string(), address.clone(), Duration::from_secs(5))
+                .with_send(b"PING\r\n".to_vec())
+                .with_expected_prefix(expect.to_vec())
+        };
+
+        assert_eq!(tcp(b"+PONG").health().await.status, IndicatorStatus::Up);
+        assert!(message(tcp(b"-ERR").health().await).starts_with("unexpected response"));
+    }
+
+    #[tokio::test]
+    async fn reports_refused_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        drop(listener);
+
+        let result = Tcp::new("tcp".to_string(), address, Duration::from_secs(5))
+            .health()
+            .await;
+        assert!(message(result).starts_with("could not connect"));
+    }
+
+    #[tokio::test]
+    async fn times_out() {
+        let address = serve(0, b"").await;
+        let result = Tcp::new("tcp".to_string(), address, Duration::from_millis(100))
+            .with_expected_prefix(b"220".to_vec())
+            .health()
+            .await;
+        assert!(message(result).contains("timed out after 100ms"));
+    }
+}
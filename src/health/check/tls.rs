@@ -0,0 +1,200 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use rocket::{figment::Figment, serde::json::json};
+
+use crate::health::{self, IndicatorResult, IndicatorStatus};
+
+use super::{CertificateSource, TlsCertificate};
+
+impl TlsCertificate {
+    /// Creates a new certificate indicator for the given PEM files.
+    /// Certificates expiring within the warning period are reported as
+    /// degraded.
+    pub fn new(name: String, paths: Vec<PathBuf>, warning: Duration) -> Self {
+        Self::from_sources(
+            name,
+            paths.into_iter().map(CertificateSource::Path).collect(),
+            warning,
+        )
+    }
+
+    /// Creates a new certificate indicator for the given PEM files or PEM
+    /// data.
+    pub fn from_sources(name: String, sources: Vec<CertificateSource>, warning: Duration) -> Self {
+        Self {
+            name,
+            sources,
+            warning,
+        }
+    }
+
+    /// Creates a new certificate indicator for the certificate chain
+    /// configured for Rocket's TLS listener in `tls.certs`, either as a
+    /// path, relative to the configuration file that sets it, or as inline
+    /// PEM data.
+    pub fn from_rocket_config(
+        name: String,
+        figment: &Figment,
+        warning: Duration,
+    ) -> Result<Self, String> {
+        let source = CertificateSource::from_rocket_config(figment)?;
+        Ok(Self::from_sources(name, vec![source], warning))
+    }
+}
+
+#[async_trait::async_trait]
+impl health::Indicator for TlsCertificate {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.health().await.into()
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        let warning = self.warning.as_secs() as i64;
+
+        let mut status = IndicatorStatus::Up;
+        let mut problems = vec![];
+        let mut certificates = vec![];
+
+        for source in &self.sources {
+            let loaded = match source.load_async().await {
+                Ok(loaded) => loaded,
+                Err(e) => {
+                    status = IndicatorStatus::Down;
+                    problems.push(e);
+                    continue;
+                }
+            };
+
+            for certificate in loaded {
+                let expires_in = certificate.not_after - now;
+
+                if expires_in <= 0 {
+                    status = IndicatorStatus::Down;
+                    problems.push(format!(
+                        "certificate {} in {} has expired",
+                        certificate.subject,
+                        source.describe()
+                    ));
+                } else if expires_in <= warning {
+                    if status == IndicatorStatus::Up {
+                        status = IndicatorStatus::Degraded;
+                    }
+                    problems.push(format!(
+                        "certificate {} in {} expires in {} days",
+                        certificate.subject,
+                        source.describe(),
+                        expires_in / 86400
+                    ));
+                }
+
+                certificates.push(json!({
+                    "path": source.describe(),
+                    "subject": certificate.subject,
+                    "expires_in_seconds": expires_in,
+                }));
+            }
+        }
+
+        let mut result = IndicatorResult::new(status).with_detail("certificates", certificates);
+        if !problems.is_empty() {
+            result = result.with_message(problems.join(", "));
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use rocket::figment::{
+        providers::{Format, Toml},
+        Figment,
+    };
+
+    use crate::health::{Indicator, IndicatorStatus};
+
+    use super::{CertificateSource, TlsCertificate};
+
+    const DAY: Duration = Duration::from_secs(86400);
+
+    fn cert(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/certs")
+            .join(name)
+    }
+
+    fn indicator(name: &str, warning: Duration) -> TlsCertificate {
+        TlsCertificate::new("tls".to_string(), vec![cert(name)], warning)
+    }
+
+    #[tokio::test]
+    async fn reports_expiry() {
+        let valid = indicator("valid.pem", 30 * DAY).health().await;
+        assert_eq!(valid.status, IndicatorStatus::Up);
+        assert!(valid.message.is_none());
+
+        let expiring = indicator("valid.pem", 365 * 200 * DAY).health().await;
+        assert_eq!(expiring.status, IndicatorStatus::Degraded);
+        assert!(expiring.message.unwrap().contains("CN=valid"));
+
+        let expired = indicator("expired.pem", 30 * DAY).health().await;
+        assert_eq!(expired.status, IndicatorStatus::Down);
+        assert!(expired.message.unwrap().ends_with("has expired"));
+
+        let missing = indicator("missing.pem", 30 * DAY).health().await;
+        assert_eq!(missing.status, IndicatorStatus::Down);
+        assert!(missing.message.unwrap().starts_with("could not read"));
+    }
+
+    #[tokio::test]
+    async fn reports_invalid_pem() {
+        let invalid = TlsCertificate::from_sources(
+            "tls".to_string(),
+            vec![CertificateSource::Pem(b"not a certificate".to_vec())],
+            30 * DAY,
+        );
+        let result = invalid.health().await;
+        assert_eq!(result.status, IndicatorStatus::Down);
+        assert_eq!(
+            result.message.unwrap(),
+            "no certificate found in inline PEM"
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_rocket_config() {
+        let figment = Figment::from(Toml::file(cert("rocket.toml")));
+        let relative =
+            TlsCertificate::from_rocket_config("tls".to_string(), &figment, DAY).unwrap();
+        let result = relative.health().await;
+        assert_eq!(result.status, IndicatorStatus::Up);
+        assert_eq!(
+            result.details["certificates"][0]["path"],
+            cert("valid.pem").display().to_string()
+        );
+
+        let pem = std::fs::read(cert("valid.pem")).unwrap();
+        let figment = Figment::from(("tls.certs", pem));
+        let inline = TlsCertificate::from_rocket_config("tls".to_string(), &figment, DAY).unwrap();
+        let result = inline.health().await;
+        assert_eq!(result.status, IndicatorStatus::Up);
+        assert_eq!(result.details["certificates"][0]["path"], "inline PEM");
+
+        assert!(
+            TlsCertificate::from_rocket_config("tls".to_string(), &Figment::new(), DAY).is_err()
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use crate::health::{self, IndicatorResult};
+
+use super::WritableDirectory;
+
+/// Distinguishes probe files of concurrent checks.
+static PROBE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+impl WritableDirectory {
+    /// Creates a new writability indicator for the given directory.
+    pub fn new(name: String, directory: PathBuf, timeout: Duration) -> Self {
+        Self {
+            name,
+            directory,
+            timeout,
+            in_flight: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+/// Clears the in-flight flag of the indicator when the probe ends, even if
+/// it panics.
+struct InFlight(Arc<AtomicBool>);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+/// Creates, fsyncs and deletes the probe file, reporting the failing
+/// syscall along with the error.
+fn probe(path: &Path) -> Result<(), (&'static str, io::Error)> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| ("open", e))?;
+
+    let written = file
+        .write_all(b"probe")
+        .map_err(|e| ("write", e))
+        .and_then(|_| file.sync_all().map_err(|e| ("fsync", e)));
+    drop(file);
+
+    let removed = fs::remove_file(path).map_err(|e| ("unlink", e));
+
+    written.and(removed)
+}
+
+#[async_trait::async_trait]
+impl health::Indicator for WritableDirectory {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.health().await.into()
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        if self.in_flight.swap(true, Ordering::AcqRel) {
+            return IndicatorResult::down(format!(
+                "previous writability probe of {} is still running",
+                self.directory.display()
+            ))
+            .with_detail("directory", self.directory.display().to_string());
+        }
+        let in_flight = InFlight(self.in_flight.clone());
+
+        let path = self.directory.join(format!(
+            ".actuator-probe-{}-{}",
+            std::process::id(),
+            PROBE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let probe_path = path.clone();
+        let result = tokio::time::timeout(
+            self.timeout,
+            tokio::task::spawn_blocking(move || {
+                let _in_flight = in_flight;
+                probe(&probe_path)
+            }),
+        )
+        .await;
+
+        let result = match result {
+            Ok(Ok(Ok(()))) => IndicatorResult::up(),
+            Ok(Ok(Err((syscall, e)))) => IndicatorResult::down(format!(
+                "{} {} failed: errno {}: {}",
+                syscall,
+                path.display(),
+                e.raw_os_error().unwrap_or_default(),
+                e
+            )),
+            Ok(Err(e)) => IndicatorResult::down(format!("writability probe failed: {}", e)),
+            Err(_) => IndicatorResult::down(format!(
+                "writability probe of {} timed out after {:?}",
+                self.directory.display(),
+                self.timeout
+            )),
+        };
+
+        result.with_detail("directory", self.directory.display().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::atomic::Ordering, time::Duration};
+
+    use crate::health::{Indicator, IndicatorStatus};
+
+    use super::WritableDirectory;
+
+    fn indicator(directory: &str) -> WritableDirectory {
+        WritableDirectory::new(
+            "writable".to_string(),
+            directory.into(),
+            Duration::from_secs(5),
+        )
+    }
+
+    #[tokio::test]
+    async fn probes_directory() {
+        let writable = indicator(std::env::temp_dir().to_str().unwrap());
+        assert_eq!(writable.health().await.status, IndicatorStatus::Up);
+        assert!(!writable.in_flight.load(Ordering::Acquire));
+
+        let result = indicator("/nonexistent/actuator").health().await;
+        assert_eq!(result.status, IndicatorStatus::Down);
+        assert!(result
+            .message
+            .unwrap()
+            .starts_with("open /nonexistent/actuator/"));
+    }
+
+    #[tokio::test]
+    async fn skips_probe_while_previous_is_running() {
+        let writable = indicator(std::env::temp_dir().to_str().unwrap());
+        writable.in_flight.store(true, Ordering::Release);
+
+        let result = writable.health().await;
+        assert_eq!(result.status, IndicatorStatus::Down);
+        assert!(result.message.unwrap().contains("still running"));
+    }
+}
@@ -0,0 +1,55 @@
+use std::future::Future;
+
+use super::{Indicator, IndicatorResult};
+
+/// Indicator backed by an async closure, see [`indicator_fn`].
+pub struct FnIndicator<F> {
+    /// The name of the indicator.
+    name: String,
+    /// The closure returning the check future.
+    f: F,
+}
+
+/// Creates an indicator that checks health by calling the given async
+/// closure. The closure may return either `Result<(), String>` or an
+/// [`IndicatorResult`]. Shared state such as connection pools should be
+/// wrapped in an `Arc` and cloned into the returned future:
+///
+/// ```ignore
+/// let pool = Arc::new(pool);
+/// let indicator = health::indicator_fn("database", move || {
+///     let pool = pool.clone();
+///     async move { pool.get().map(|_| ()).map_err(|e| e.to_string()) }
+/// });
+/// ```
+pub fn indicator_fn<F, Fut, R>(name: impl Into<String>, f: F) -> Box<dyn Indicator>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = R> + Send,
+    R: Into<IndicatorResult>,
+{
+    Box::new(FnIndicator {
+        name: name.into(),
+        f,
+    })
+}
+
+#[async_trait::async_trait]
+impl<F, Fut, R> Indicator for FnIndicator<F>
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = R> + Send,
+    R: Into<IndicatorResult>,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.health().await.into()
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        (self.f)().await.into()
+    }
+}
@@ -0,0 +1,29 @@
+use super::{IndicatorStatus, Listener, LogListener, Transition};
+
+impl Listener for LogListener {
+    fn on_transition(&self, transition: &Transition) {
+        let change = match transition.old {
+            Some(old) => format!("changed from {:?} to {:?}", old, transition.new),
+            None => format!("initially {:?}", transition.new),
+        };
+        let message = transition.message.unwrap_or("no message");
+
+        if transition.new == IndicatorStatus::Up {
+            log::info!(
+                "{} indicator {} {}: {}",
+                transition.group,
+                transition.indicator,
+                change,
+                message
+            );
+        } else {
+            log::warn!(
+                "{} indicator {} {}: {}",
+                transition.group,
+                transition.indicator,
+                change,
+                message
+            );
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use rocket::{
+    delete, get, http::Status, put, response::status::Custom, routes, serde::json::Json, Build,
+    Rocket, State,
+};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::{
+    actuator::{join_path, RocketConfigurerer},
+    auth::{AccessPolicy, Authorized, BearerToken, Denied, Protected},
+};
+
+use super::{IndicatorResult, IndicatorStatus};
+
+/// An administrative override of the status of an indicator, e.g. to force
+/// a flapping non-essential indicator up during an incident, or to force an
+/// instance down for investigation.
+#[derive(Clone, Debug, Serialize)]
+pub struct Override {
+    /// The status reported instead of evaluating the indicator.
+    status: IndicatorStatus,
+    /// Why the override was set.
+    reason: Option<String>,
+    /// When the override expires.
+    #[serde(serialize_with = "serialize_time")]
+    expires_at: SystemTime,
+}
+
+/// Handle to the active overrides, keyed by indicator name. Cloned handles
+/// share the same overrides, so the same handle can be given to health
+/// groups and to the override endpoint.
+#[derive(Clone, Default)]
+pub struct Overrides {
+    overrides: Arc<RwLock<HashMap<String, Override>>>,
+}
+
+/// Exposes endpoints to list, set and clear overrides, by default under
+/// `/health/overrides`:
+///
+/// - `GET /health/overrides`
+///
+/// - `PUT /health/overrides/<name>` with a JSON body such as
+///   `{"status": "Up", "reason": "flapping", "ttl_seconds": 3600}`
+///
+/// - `DELETE /health/overrides/<name>`
+///
+/// All endpoints require an `Authorization: Bearer <token>` header, unless
+/// another access policy is given.
+pub struct OverrideEndpoint {
+    overrides: Overrides,
+    /// The policy requests to the endpoints must satisfy.
+    access_policy: Arc<dyn AccessPolicy>,
+    /// The path of the endpoints, relative to the base path of the actuator.
+    path: String,
+}
+
+/// Error returned when setting an override with a time to live that is
+/// zero or too large.
+#[derive(Debug)]
+pub struct InvalidTtl {
+    ttl: Duration,
+}
+
+#[derive(Deserialize)]
+struct OverrideRequest {
+    status: IndicatorStatus,
+    reason: Option<String>,
+    ttl_seconds: u64,
+}
+
+fn serialize_time<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&super::format_time(*time))
+}
+
+impl Override {
+    pub fn status(&self) -> IndicatorStatus {
+        self.status
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    pub fn expires_at(&self) -> SystemTime {
+        self.expires_at
+    }
+
+    fn is_expired(&self) -> bool {
+        self.expires_at <= SystemTime::now()
+    }
+}
+
+impl From<&Override> for IndicatorResult {
+    fn from(value: &Override) -> Self {
+        let mut result = IndicatorResult::new(value.status)
+            .with_detail("override_expires_at", super::format_time(value.expires_at));
+        result.message = value.reason.clone();
+        result.overridden = true;
+        result
+    }
+}
+
+impl InvalidTtl {
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+}
+
+impl fmt::Display for InvalidTtl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid override ttl {:?}", self.ttl)
+    }
+}
+
+impl std::error::Error for InvalidTtl {}
+
+impl Overrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the status of the indicator with the given name until the
+    /// time to live elapses. Replaces any existing override. Fails if the
+    /// time to live is zero or the expiry is not representable.
+    pub fn set(
+        &self,
+        name: &str,
+        status: IndicatorStatus,
+        reason: Option<String>,
+        ttl: Duration,
+    ) -> Result<Override, InvalidTtl> {
+        let expires_at = SystemTime::now()
+            .checked_add(ttl)
+            .filter(|_| !ttl.is_zero())
+            .ok_or(InvalidTtl { ttl })?;
+
+        let value = Override {
+            status,
+            reason,
+            expires_at,
+        };
+
+        self.overrides
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string(), value.clone());
+
+        Ok(value)
+    }
+
+    /// Clears the override of the indicator with the given name, returning
+    /// it if it was active.
+    pub fn clear(&self, name: &str) -> Option<Override> {
+        self.overrides
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(name)
+            .filter(|o| !o.is_expired())
+    }
+
+    /// Returns the active override of the indicator with the given name.
+    pub fn get(&self, name: &str) -> Option<Override> {
+        self.overrides
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
+            .filter(|o| !o.is_expired())
+            .cloned()
+    }
+
+    /// Returns all active overrides, discarding expired ones.
+    pub fn list(&self) -> HashMap<String, Override> {
+        let mut overrides = self.overrides.write().unwrap_or_else(|e| e.into_inner());
+        overrides.retain(|_, o| !o.is_expired());
+        overrides.clone()
+    }
+}
+
+impl OverrideEndpoint {
+    pub fn new(overrides: Overrides, token: String) -> Self {
+        Self {
+            overrides,
+            access_policy: Arc::new(BearerToken::new(token)),
+            path: "/health/overrides".to_string(),
+        }
+    }
+
+    /// Replaces the bearer token policy of the override endpoints with the
+    /// given policy.
+    pub fn with_access_policy(mut self, policy: impl AccessPolicy + 'static) -> Self {
+        self.access_policy = Arc::new(policy);
+        self
+    }
+
+    /// Sets the path of the override endpoints, relative to the base path of
+    /// the actuator. Defaults to `/health/overrides`.
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path = path.to_string();
+        self
+    }
+}
+
+impl Protected for OverrideEndpoint {
+    fn access_policy(&self) -> Option<&Arc<dyn AccessPolicy>> {
+        Some(&self.access_policy)
+    }
+}
+
+#[get("/")]
+fn list(
+    auth: Result<Authorized<OverrideEndpoint>, Denied>,
+    endpoint: &State<OverrideEndpoint>,
+) -> Result<Json<HashMap<String, Override>>, Denied> {
+    auth?;
+
+    Ok(Json(endpoint.overrides.list()))
+}
+
+#[put("/<name>", data = "<request>")]
+fn set(
+    auth: Result<Authorized<OverrideEndpoint>, Denied>,
+    endpoint: &State<OverrideEndpoint>,
+    name: &str,
+    request: Json<OverrideRequest>,
+) -> Result<Result<Json<Override>, Custom<String>>, Denied> {
+    auth?;
+
+    let request = request.into_inner();
+    Ok(endpoint
+        .overrides
+        .set(
+            name,
+            request.status,
+            request.reason,
+            Duration::from_secs(request.ttl_seconds),
+        )
+        .map(Json)
+        .map_err(|e| Custom(Status::BadRequest, e.to_string())))
+}
+
+#[delete("/<name>")]
+fn clear(
+    auth: Result<Authorized<OverrideEndpoint>, Denied>,
+    endpoint: &State<OverrideEndpoint>,
+    name: &str,
+) -> Result<Status, Denied> {
+    auth?;
+
+    match endpoint.overrides.clear(name) {
+        Some(_) => Ok(Status::NoContent),
+        None => Ok(Status::NotFound),
+    }
+}
+
+impl RocketConfigurerer for OverrideEndpoint {
+    fn configure(self, rocket: Rocket<Build>, base_path: &str) -> Rocket<Build> {
+        let path = join_path(base_path, &self.path);
+        rocket.manage(self).mount(path, routes![list, set, clear])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rocket::{
+        http::{ContentType, Header, Status},
+        local::blocking::Client,
+    };
+
+    use crate::{actuate, health::IndicatorStatus};
+
+    use super::Overrides;
+
+    fn client(overrides: Overrides) -> Client {
+        Client::tracked(
+            actuate(rocket::build())
+                .with_overrides(overrides, "token".to_string())
+                .get(),
+        )
+        .unwrap()
+    }
+
+    fn put(client: &Client, body: &str) -> Status {
+        client
+            .put("/health/overrides/db")
+            .header(Header::new("Authorization", "Bearer token"))
+            .header(ContentType::JSON)
+            .body(body)
+            .dispatch()
+            .status()
+    }
+
+    #[test]
+    fn rejects_invalid_ttl() {
+        let overrides = Overrides::new();
+        assert!(overrides
+            .set("db", IndicatorStatus::Up, None, Duration::ZERO)
+            .is_err());
+        assert!(overrides
+            .set("db", IndicatorStatus::Up, None, Duration::MAX)
+            .is_err());
+        assert!(overrides.get("db").is_none());
+
+        let client = client(overrides);
+        assert_eq!(
+            put(
+                &client,
+                r#"{"status": "Up", "ttl_seconds": 18446744073709551615}"#
+            ),
+            Status::BadRequest
+        );
+        assert_eq!(
+            put(&client, r#"{"status": "Up", "ttl_seconds": 0}"#),
+            Status::BadRequest
+        );
+    }
+
+    #[test]
+    fn sets_lists_and_clears_overrides() {
+        let overrides = Overrides::new();
+        let client = client(overrides.clone());
+
+        assert_eq!(
+            put(
+                &client,
+                r#"{"status": "Down", "reason": "investigating", "ttl_seconds": 60}"#
+            ),
+            Status::Ok
+        );
+        let active = overrides.get("db").unwrap();
+        assert_eq!(active.status(), IndicatorStatus::Down);
+        assert_eq!(active.reason(), Some("investigating"));
+
+        let list = client
+            .get("/health/overrides")
+            .header(Header::new("Authorization", "Bearer token"))
+            .dispatch()
+            .into_string()
+            .unwrap();
+        assert!(list.contains("investigating"));
+
+        let clear = |client: &Client| {
+            client
+                .delete("/health/overrides/db")
+                .header(Header::new("Authorization", "Bearer token"))
+                .dispatch()
+                .status()
+        };
+        assert_eq!(clear(&client), Status::NoContent);
+        assert_eq!(clear(&client), Status::NotFound);
+    }
+
+    #[test]
+    fn requires_token() {
+        let client = client(Overrides::new());
+
+        let response = client.get("/health/overrides").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_eq!(
+            response.headers().get_one("WWW-Authenticate"),
+            Some("Bearer")
+        );
+        assert_eq!(
+            client
+                .get("/health/overrides")
+                .header(Header::new("Authorization", "Bearer other"))
+                .dispatch()
+                .status(),
+            Status::Unauthorized
+        );
+    }
+}
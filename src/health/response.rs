@@ -0,0 +1,422 @@
+use std::cmp::Ordering;
+
+use rocket::{
+    http::{Accept, ContentType, MediaType, Status},
+    response::{self, Responder},
+    serde::json::{json, serde_json::Map, Json, Value},
+    Request, Response,
+};
+
+use super::{HealthResponse, IndicatorStatus};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Json,
+    HealthJson,
+    SpringBoot,
+    Text,
+}
+
+impl Format {
+    fn from_media_type(media_type: &MediaType) -> Option<Self> {
+        match (media_type.top().as_str(), media_type.sub().as_str()) {
+            ("application", "json") => Some(Self::Json),
+            ("application", "health+json") => Some(Self::HealthJson),
+            ("application", "vnd.spring-boot.actuator.v3+json")
+            | ("application", "vnd.spring-boot.actuator.v2+json") => Some(Self::SpringBoot),
+            ("text", "plain") => Some(Self::Text),
+            _ => None,
+        }
+    }
+
+    /// Picks the supported format with the highest weight in the `Accept`
+    /// header, defaulting to JSON. Media types with a weight of 0 are not
+    /// acceptable, `None` is returned when JSON is refused that way and no
+    /// other supported format is accepted.
+    fn negotiate(accept: Option<&Accept>) -> Option<Self> {
+        let Some(accept) = accept else {
+            return Some(Self::Json);
+        };
+
+        let (mut media_types, refused): (Vec<_>, Vec<_>) =
+            accept.iter().partition(|m| m.weight_or(1.0) > 0.0);
+        media_types.sort_by(|a, b| {
+            b.weight_or(1.0)
+                .partial_cmp(&a.weight_or(1.0))
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let json_refused = refused
+            .iter()
+            .any(|m| Self::from_media_type(m.media_type()) == Some(Self::Json));
+
+        media_types
+            .into_iter()
+            .find_map(|m| Self::from_media_type(m.media_type()))
+            .or((!json_refused).then_some(Self::Json))
+    }
+}
+
+fn http_status(status: IndicatorStatus) -> Status {
+    if status.is_failure() {
+        Status::ServiceUnavailable
+    } else {
+        Status::Ok
+    }
+}
+
+fn health_json_status(status: IndicatorStatus) -> &'static str {
+    match status {
+        IndicatorStatus::Up => "pass",
+        IndicatorStatus::Unknown | IndicatorStatus::Degraded => "warn",
+        IndicatorStatus::OutOfService | IndicatorStatus::Down => "fail",
+    }
+}
+
+/// Derives the `observedUnit` of a detail from the unit suffix of its name,
+/// e.g. `latency_ms`.
+fn observed_unit(key: &str) -> Option<&'static str> {
+    [("_ms", "ms"), ("_seconds", "s"), ("_bytes", "bytes")]
+        .into_iter()
+        .find(|(suffix, _)| key.ends_with(suffix))
+        .map(|(_, unit)| unit)
+}
+
+fn spring_boot_status(status: IndicatorStatus) -> &'static str {
+    match status {
+        IndicatorStatus::Up => "UP",
+        IndicatorStatus::Unknown => "UNKNOWN",
+        IndicatorStatus::Degraded => "DEGRADED",
+        IndicatorStatus::OutOfService => "OUT_OF_SERVICE",
+        IndicatorStatus::Down => "DOWN",
+    }
+}
+
+impl HealthResponse {
+    fn to_health_json(&self) -> Value {
+        if self.details_hidden {
+            return json!({ "status": health_json_status(self.status) });
+        }
+
+        let time = super::format_time(self.time);
+
+        let mut checks = Map::new();
+        for (name, result) in &self.indicators {
+            let mut check = Map::new();
+            check.insert(
+                "status".to_string(),
+                health_json_status(result.status).into(),
+            );
+            if let Some(message) = &result.message {
+                check.insert("output".to_string(), message.clone().into());
+            }
+            if result.overridden {
+                check.insert("overridden".to_string(), true.into());
+            }
+            check.insert("time".to_string(), time.clone().into());
+
+            if result.details.is_empty() {
+                checks.insert(name.clone(), json!([check]));
+                continue;
+            }
+
+            // A check has a single observed value, each detail is reported
+            // as a `<component>:<measurement>` check
+            for (key, value) in &result.details {
+                let mut measurement = check.clone();
+                measurement.insert("observedValue".to_string(), value.clone());
+                if let Some(unit) = observed_unit(key) {
+                    measurement.insert("observedUnit".to_string(), unit.into());
+                }
+                checks.insert(format!("{}:{}", name, key), json!([measurement]));
+            }
+        }
+
+        json!({
+            "status": health_json_status(self.status),
+            "checks": checks,
+        })
+    }
+
+    fn to_spring_boot(&self) -> Value {
+        if self.details_hidden {
+            return json!({ "status": spring_boot_status(self.status) });
+        }
+
+        let components: Map<String, Value> = self
+            .indicators
+            .iter()
+            .map(|(name, result)| {
+                let mut details = result.details.clone();
+                if let Some(message) = &result.message {
+                    details.insert("message".to_string(), message.clone().into());
+                }
+                if result.overridden {
+                    details.insert("overridden".to_string(), true.into());
+                }
+
+                let mut component = Map::new();
+                component.insert(
+                    "status".to_string(),
+                    spring_boot_status(result.status).into(),
+                );
+                if !details.is_empty() {
+                    component.insert("details".to_string(), Value::Object(details));
+                }
+
+                (name.clone(), Value::Object(component))
+            })
+            .collect();
+
+        json!({
+            "status": spring_boot_status(self.status),
+            "components": components,
+        })
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!("{}\n", spring_boot_status(self.status));
+        for (name, result) in &self.indicators {
+            text.push_str(&format!("{}: {}", name, spring_boot_status(result.status)));
+            if let Some(message) = &result.message {
+                text.push_str(&format!(" ({})", message));
+            }
+            if result.overridden {
+                text.push_str(" [overridden]");
+            }
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+impl<'r> Responder<'r, 'static> for HealthResponse {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let status = http_status(self.status);
+
+        let Some(format) = Format::negotiate(req.accept()) else {
+            return Err(Status::NotAcceptable);
+        };
+
+        let response = match format {
+            Format::Json if self.details_hidden => {
+                Json(json!({ "status": self.status })).respond_to(req)?
+            }
+            Format::Json => Json(self.indicators).respond_to(req)?,
+            Format::HealthJson => {
+                Response::build_from(Json(self.to_health_json()).respond_to(req)?)
+                    .header(ContentType::new("application", "health+json"))
+                    .finalize()
+            }
+            Format::SpringBoot => {
+                Response::build_from(Json(self.to_spring_boot()).respond_to(req)?)
+                    .header(ContentType::new(
+                        "application",
+                        "vnd.spring-boot.actuator.v3+json",
+                    ))
+                    .finalize()
+            }
+            Format::Text => self.to_text().respond_to(req)?,
+        };
+
+        Response::build_from(response).status(status).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rocket::{
+        get,
+        http::{Accept, ContentType, Header, Status},
+        local::blocking::Client,
+        routes,
+        serde::json::{json, Value},
+    };
+
+    use crate::health::{HealthResponse, IndicatorResult, IndicatorStatus};
+
+    fn response() -> HealthResponse {
+        let mut indicators = BTreeMap::new();
+        indicators.insert(
+            "db".to_string(),
+            IndicatorResult::down("connection refused".to_string()).with_detail("latency_ms", 12),
+        );
+        indicators.insert("cache".to_string(), IndicatorResult::up());
+        HealthResponse::new(indicators)
+    }
+
+    #[get("/")]
+    fn health() -> HealthResponse {
+        response()
+    }
+
+    #[get("/hidden")]
+    fn hidden() -> HealthResponse {
+        response().without_details()
+    }
+
+    #[get("/hidden/degraded")]
+    fn hidden_degraded() -> HealthResponse {
+        let mut indicators = BTreeMap::new();
+        indicators.insert(
+            "db".to_string(),
+            IndicatorResult::new(IndicatorStatus::Degraded),
+        );
+        HealthResponse::new(indicators).without_details()
+    }
+
+    fn client() -> Client {
+        Client::tracked(rocket::build().mount("/", routes![health, hidden, hidden_degraded]))
+            .unwrap()
+    }
+
+    fn get(client: &Client, path: &str, accept: &str) -> (Status, Option<ContentType>, String) {
+        let response = client
+            .get(path)
+            .header(Header::new("Accept", accept.to_string()))
+            .dispatch();
+        (
+            response.status(),
+            response.content_type(),
+            response.into_string().unwrap_or_default(),
+        )
+    }
+
+    #[test]
+    fn defaults_to_json_indicator_map() {
+        let client = client();
+        let response = client.get("/").dispatch();
+
+        assert_eq!(response.status(), Status::ServiceUnavailable);
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+        let body: Value = response.into_json().unwrap();
+        assert_eq!(body["db"]["status"], "Down");
+        assert_eq!(body["db"]["message"], "connection refused");
+        assert_eq!(body["db"]["details"]["latency_ms"], 12);
+        assert_eq!(body["cache"]["status"], "Up");
+    }
+
+    #[test]
+    fn renders_health_json() {
+        let (status, content_type, body) = get(&client(), "/", "application/health+json");
+
+        assert_eq!(status, Status::ServiceUnavailable);
+        assert_eq!(
+            content_type,
+            Some(ContentType::new("application", "health+json"))
+        );
+        let body: Value = rocket::serde::json::from_str(&body).unwrap();
+        assert_eq!(body["status"], "fail");
+        let check = &body["checks"]["db:latency_ms"][0];
+        assert_eq!(check["status"], "fail");
+        assert_eq!(check["output"], "connection refused");
+        assert_eq!(check["observedValue"], 12);
+        assert_eq!(check["observedUnit"], "ms");
+        assert!(check["time"].is_string());
+        assert!(body["checks"].get("db").is_none());
+        let check = &body["checks"]["cache"][0];
+        assert_eq!(check["status"], "pass");
+        assert!(check.get("observedValue").is_none());
+        assert!(check["time"].is_string());
+    }
+
+    #[test]
+    fn renders_spring_boot() {
+        let (status, _, body) = get(
+            &client(),
+            "/",
+            "application/vnd.spring-boot.actuator.v3+json",
+        );
+
+        assert_eq!(status, Status::ServiceUnavailable);
+        let body: Value = rocket::serde::json::from_str(&body).unwrap();
+        assert_eq!(body["status"], "DOWN");
+        assert_eq!(
+            body["components"]["db"],
+            json!({
+                "status": "DOWN",
+                "details": { "latency_ms": 12, "message": "connection refused" },
+            })
+        );
+        assert_eq!(body["components"]["cache"], json!({ "status": "UP" }));
+    }
+
+    #[test]
+    fn renders_text() {
+        let (status, content_type, body) = get(&client(), "/", "text/plain");
+
+        assert_eq!(status, Status::ServiceUnavailable);
+        assert_eq!(content_type, Some(ContentType::Plain));
+        assert_eq!(body, "DOWN\ncache: UP\ndb: DOWN (connection refused)\n");
+    }
+
+    #[test]
+    fn negotiates_by_weight() {
+        let (_, _, body) = get(&client(), "/", "application/json;q=0.5, text/plain");
+        assert!(body.starts_with("DOWN\n"));
+
+        let (_, content_type, _) = get(&client(), "/", "image/png");
+        assert_eq!(content_type, Some(ContentType::JSON));
+    }
+
+    #[test]
+    fn excludes_refused_media_types() {
+        let (_, content_type, _) = get(&client(), "/", "text/plain;q=0, image/png");
+        assert_eq!(content_type, Some(ContentType::JSON));
+
+        let (_, content_type, _) = get(&client(), "/", "text/plain;q=0, */*");
+        assert_eq!(content_type, Some(ContentType::JSON));
+
+        let (status, _, _) = get(&client(), "/", "application/json;q=0");
+        assert_eq!(status, Status::NotAcceptable);
+
+        let (_, _, body) = get(&client(), "/", "application/json;q=0, text/plain;q=0.1");
+        assert!(body.starts_with("DOWN\n"));
+    }
+
+    #[test]
+    fn hides_details_in_all_formats() {
+        let client = client();
+
+        let response = client.get("/hidden").header(Accept::JSON).dispatch();
+        assert_eq!(response.status(), Status::ServiceUnavailable);
+        assert_eq!(response.into_string().unwrap(), r#"{"status":"Down"}"#);
+
+        let response = client.get("/hidden").dispatch();
+        assert_eq!(response.into_string().unwrap(), r#"{"status":"Down"}"#);
+
+        let (_, _, body) = get(&client, "/hidden", "application/health+json");
+        assert_eq!(body, r#"{"status":"fail"}"#);
+
+        let (_, _, body) = get(
+            &client,
+            "/hidden",
+            "application/vnd.spring-boot.actuator.v3+json",
+        );
+        assert_eq!(body, r#"{"status":"DOWN"}"#);
+
+        let (_, _, body) = get(&client, "/hidden", "text/plain");
+        assert_eq!(body, "DOWN\n");
+
+        // Degraded responds with 200 like up, the body tells them apart
+        let response = client.get("/hidden/degraded").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), r#"{"status":"Degraded"}"#);
+    }
+
+    #[test]
+    fn maps_statuses() {
+        for (status, code) in [
+            (IndicatorStatus::Up, Status::Ok),
+            (IndicatorStatus::Unknown, Status::Ok),
+            (IndicatorStatus::Degraded, Status::Ok),
+            (IndicatorStatus::OutOfService, Status::ServiceUnavailable),
+            (IndicatorStatus::Down, Status::ServiceUnavailable),
+        ] {
+            assert_eq!(super::http_status(status), code);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use super::{Indicator, IndicatorResult};
+
+/// Indicator that evaluates the wrapped indicator again when it fails, so
+/// that a single dropped packet does not fail the health group. Waits
+/// between attempts with exponential backoff, and reports down when the
+/// attempts do not complete within the timeout. The number of attempts
+/// is reported in the `attempts` detail.
+pub struct RetryIndicator {
+    /// The wrapped indicator.
+    inner: Box<dyn Indicator>,
+    /// The maximum number of attempts, including the first one.
+    max_attempts: u32,
+    /// The wait before the second attempt, doubled for every further
+    /// attempt.
+    backoff: Duration,
+    /// The maximum duration of all attempts and the waits between them.
+    timeout: Duration,
+}
+
+impl RetryIndicator {
+    pub fn new(
+        indicator: Box<dyn Indicator>,
+        max_attempts: u32,
+        backoff: Duration,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            inner: indicator,
+            max_attempts: max_attempts.max(1),
+            backoff,
+            timeout,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Indicator for RetryIndicator {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.health().await.into()
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        let deadline = Instant::now() + self.timeout;
+        let mut backoff = self.backoff;
+        let mut attempt = 1;
+
+        loop {
+            let result = match tokio::time::timeout_at(deadline, self.inner.health()).await {
+                Ok(result) => result,
+                Err(_) => IndicatorResult::down(format!(
+                    "attempt {} did not complete within {:?}",
+                    attempt, self.timeout
+                )),
+            };
+
+            // Stop when there is no time left for another attempt
+            if !result.status().is_failure()
+                || attempt >= self.max_attempts
+                || Instant::now() + backoff >= deadline
+            {
+                return result.with_detail("attempts", attempt);
+            }
+
+            tokio::time::sleep(backoff).await;
+            backoff = backoff.saturating_mul(2);
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use crate::health::{indicator_fn, Indicator, IndicatorStatus};
+
+    use super::RetryIndicator;
+
+    /// Returns an indicator failing the given number of times.
+    fn flaky(failures: u32) -> (Box<dyn Indicator>, Arc<AtomicU32>) {
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+        let indicator = indicator_fn("flaky", move || {
+            let call = counter.fetch_add(1, Ordering::Relaxed);
+            async move {
+                if call < failures {
+                    Err("connection reset".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+        });
+        (indicator, calls)
+    }
+
+    #[tokio::test]
+    async fn retries_until_up() {
+        let (indicator, calls) = flaky(2);
+        let retry = RetryIndicator::new(
+            indicator,
+            3,
+            Duration::from_millis(1),
+            Duration::from_secs(5),
+        );
+
+        let result = retry.health().await;
+        assert_eq!(result.status, IndicatorStatus::Up);
+        assert_eq!(result.details["attempts"], 3);
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn stops_after_max_attempts() {
+        let (indicator, calls) = flaky(5);
+        let retry = RetryIndicator::new(
+            indicator,
+            2,
+            Duration::from_millis(1),
+            Duration::from_secs(5),
+        );
+
+        let result = retry.health().await;
+        assert_eq!(result.status, IndicatorStatus::Down);
+        assert_eq!(result.message.unwrap(), "connection reset");
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn stops_when_backoff_exceeds_timeout() {
+        let (indicator, calls) = flaky(5);
+        let retry = RetryIndicator::new(
+            indicator,
+            5,
+            Duration::from_secs(10),
+            Duration::from_secs(1),
+        );
+
+        let result = retry.health().await;
+        assert_eq!(result.status, IndicatorStatus::Down);
+        assert_eq!(result.details["attempts"], 1);
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+}
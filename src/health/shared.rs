@@ -0,0 +1,118 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures::{
+    future::{BoxFuture, Shared},
+    FutureExt,
+};
+
+use super::{Indicator, IndicatorResult};
+
+/// Indicator that evaluates the wrapped indicator at most once per
+/// evaluation window. Checks within the window, including concurrent
+/// checks from different health groups, share the same result.
+pub struct SharedIndicator {
+    /// The wrapped indicator.
+    inner: Arc<dyn Indicator>,
+    /// How long a result is reused after the evaluation started.
+    window: Duration,
+    /// The start time and the, possibly still running, latest evaluation.
+    last: Mutex<Option<(Instant, Shared<BoxFuture<'static, IndicatorResult>>)>>,
+}
+
+impl SharedIndicator {
+    pub fn new(indicator: Box<dyn Indicator>, window: Duration) -> Self {
+        Self {
+            inner: Arc::from(indicator),
+            window,
+            last: Mutex::new(None),
+        }
+    }
+
+    fn evaluation(&self) -> Shared<BoxFuture<'static, IndicatorResult>> {
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some((started, evaluation)) = last.as_ref() {
+            if started.elapsed() < self.window {
+                return evaluation.clone();
+            }
+        }
+
+        let inner = self.inner.clone();
+        let evaluation = async move { inner.health().await }.boxed().shared();
+        *last = Some((Instant::now(), evaluation.clone()));
+
+        evaluation
+    }
+}
+
+#[async_trait::async_trait]
+impl Indicator for SharedIndicator {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.health().await.into()
+    }
+
+    async fn health(&self) -> IndicatorResult {
+        self.evaluation().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use crate::health::{check::Group, indicator_fn, Indicator, IndicatorResult, IndicatorStatus};
+
+    use super::SharedIndicator;
+
+    /// Returns an indicator counting its evaluations, each taking a while
+    /// so that concurrent checks overlap.
+    fn counting(evaluations: &Arc<AtomicUsize>) -> Box<dyn Indicator> {
+        let evaluations = evaluations.clone();
+        indicator_fn("db", move || {
+            evaluations.fetch_add(1, Ordering::SeqCst);
+            async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                IndicatorResult::new(IndicatorStatus::Up)
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn evaluates_once_per_window() {
+        let evaluations = Arc::new(AtomicUsize::new(0));
+        let shared = Arc::new(SharedIndicator::new(
+            counting(&evaluations),
+            Duration::from_millis(200),
+        ));
+        let liveness = Group::new("liveness".to_string(), vec![Box::new(shared.clone())]).unwrap();
+        let readiness =
+            Group::new("readiness".to_string(), vec![Box::new(shared.clone())]).unwrap();
+
+        let (live, ready, direct) =
+            tokio::join!(liveness.check(), readiness.check(), shared.health());
+        assert_eq!(evaluations.load(Ordering::SeqCst), 1);
+        assert_eq!(live.indicators["db"].status, IndicatorStatus::Up);
+        assert_eq!(ready.indicators["db"].status, IndicatorStatus::Up);
+        assert_eq!(direct.status, IndicatorStatus::Up);
+
+        liveness.check().await;
+        assert_eq!(evaluations.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        tokio::join!(liveness.check(), readiness.check());
+        assert_eq!(evaluations.load(Ordering::SeqCst), 2);
+    }
+}
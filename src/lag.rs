@@ -0,0 +1,141 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Weak,
+    },
+    time::{Duration, Instant},
+};
+
+/// Measures the scheduling delay of the tokio runtime. A background task
+/// sleeps on a fixed tick and records how much later than requested it was
+/// woken up. A blocked or starved runtime wakes the task late, or not at
+/// all, which is reflected in [`LagMonitor::lag`].
+///
+/// Cloned monitors share the same measurements, the background task stops
+/// when all clones are dropped.
+#[derive(Clone)]
+pub struct LagMonitor {
+    state: Arc<LagState>,
+}
+
+struct LagState {
+    /// The tick of the background task.
+    tick: Duration,
+    /// The time the monitor was started, reference for `last_tick_micros`.
+    started: Instant,
+    /// The time of the last tick, in microseconds since `started`.
+    last_tick_micros: AtomicU64,
+    /// The lag measured on the last tick, in microseconds.
+    lag_micros: AtomicU64,
+}
+
+impl LagMonitor {
+    /// Starts measuring the lag of the current runtime with the given tick.
+    /// Must be called within a tokio runtime.
+    pub fn start(tick: Duration) -> Self {
+        let state = Arc::new(LagState {
+            tick,
+            started: Instant::now(),
+            last_tick_micros: AtomicU64::new(0),
+            lag_micros: AtomicU64::new(0),
+        });
+
+        tokio::spawn(Self::run(Arc::downgrade(&state), tick));
+
+        Self { state }
+    }
+
+    async fn run(state: Weak<LagState>, tick: Duration) {
+        loop {
+            let start = Instant::now();
+            tokio::time::sleep(tick).await;
+            let lag = start.elapsed().saturating_sub(tick);
+
+            let Some(state) = state.upgrade() else {
+                return;
+            };
+            state
+                .lag_micros
+                .store(lag.as_micros() as u64, Ordering::Relaxed);
+            state.last_tick_micros.store(
+                state.started.elapsed().as_micros() as u64,
+                Ordering::Relaxed,
+            );
+        }
+    }
+
+    /// Returns the current lag: the lag measured on the last tick, or the
+    /// time the next tick is overdue by if that is greater.
+    pub fn lag(&self) -> Duration {
+        let measured = Duration::from_micros(self.state.lag_micros.load(Ordering::Relaxed));
+
+        let last_tick = Duration::from_micros(self.state.last_tick_micros.load(Ordering::Relaxed));
+        let overdue = self
+            .state
+            .started
+            .elapsed()
+            .saturating_sub(last_tick)
+            .saturating_sub(self.state.tick);
+
+        measured.max(overdue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::LagMonitor;
+
+    const TICK: Duration = Duration::from_millis(10);
+
+    // The tests run on a current thread runtime, blocking the test blocks the
+    // runtime and thereby the monitor
+    #[tokio::test]
+    async fn measures_blocked_runtime() {
+        let monitor = LagMonitor::start(TICK);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(
+            monitor.lag() < Duration::from_millis(40),
+            "{:?}",
+            monitor.lag()
+        );
+
+        std::thread::sleep(Duration::from_millis(200));
+        // The next tick is overdue while the runtime is still blocked
+        assert!(
+            monitor.lag() >= Duration::from_millis(150),
+            "{:?}",
+            monitor.lag()
+        );
+
+        tokio::time::sleep(TICK / 2).await;
+        // The late tick reports the lag once the runtime is unblocked
+        assert!(
+            monitor.lag() >= Duration::from_millis(150),
+            "{:?}",
+            monitor.lag()
+        );
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(
+            monitor.lag() < Duration::from_millis(40),
+            "{:?}",
+            monitor.lag()
+        );
+    }
+
+    #[tokio::test]
+    async fn stops_when_dropped() {
+        let monitor = LagMonitor::start(TICK);
+        let state = std::sync::Arc::downgrade(&monitor.state);
+        let clone = monitor.clone();
+
+        drop(monitor);
+        tokio::time::sleep(TICK * 3).await;
+        assert!(state.upgrade().is_some());
+
+        drop(clone);
+        assert!(state.upgrade().is_none());
+    }
+}
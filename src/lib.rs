@@ -10,8 +10,52 @@
 //!
 //!   - [Tonic Health](https://github.com/hyperium/tonic/tree/master/tonic-health)
 //!
+//!   - [Redis](https://github.com/redis-rs/redis-rs) health
+//!
+//!   - Disk space and filesystem writability
+//!
+//!   - TCP connectivity
+//!
+//!   - HTTP dependencies
+//!
+//!   - External commands
+//!
+//!   - TLS certificate expiry
+//!
+//!   - Tokio event loop lag
+//!
+//!   - Background worker heartbeats
+//!
+//!   - Supervised background tasks
+//!
+//!   - Readiness gates for startup tasks
+//!
 //!   - Customizable indicators
 //!
+//!   - JSON, `application/health+json`, Spring Boot and plain text responses based on the `Accept` header
+//!
+//!   - Indicator status transition listeners
+//!
+//!   - Adding and removing indicators at runtime
+//!
+//!   - Administrative overrides to force indicators up or down
+//!
+//!   - Shared indicators evaluated once across liveness and readiness
+//!
+//!   - Dependencies between indicators, skipping indicators whose dependency is down
+//!
+//!   - Startup grace period reporting failing indicators as starting
+//!
+//!   - Retries with exponential backoff for flaky indicators
+//!
+//!   - Concurrency limit with indicator priorities and evaluation deadline per health group
+//!
+//!   - Indicator detail visibility per health group: never, when authorized or always
+//!
+//! - Configurable base path and per endpoint paths, e.g. `/actuator/health/liveness`
+//!
+//! - Per endpoint access policies: bearer token, HTTP basic, client IP allowlist or a custom request guard
+//!
 //! - [Prometheus](http://prometheus.io) metric collection
 //!
 //!   - Rocket http request metrics
@@ -20,7 +64,13 @@
 //!
 //!   - r2d2 connection pool metrics
 //!
-//!   - Tokio runtime metrics
+//!   - Tokio runtime and event loop lag metrics
+//!
+//!   - Disk space metrics
+//!
+//!   - TLS certificate expiry metrics
+//!
+//!   - Supervised background task metrics
 //!
 //!
 //! ## Installation
@@ -42,12 +92,38 @@
 //!
 //! health-diesel: includes diesel health indicator
 //!
+//! health-disk: includes disk space health indicator
+//!
+//! health-writable: includes filesystem writability health indicator
+//!
+//! health-tcp: includes TCP connect health indicator
+//!
+//! health-http: includes HTTP dependency health indicator
+//!
+//! health-redis: includes redis health indicator
+//!
+//! health-command: includes external command health indicator
+//!
+//! health-tls: includes TLS certificate expiry health indicator
+//!
+//! health-tokio: includes tokio event loop lag health indicator
+//!
+//! health-macros: includes the `#[indicator]` attribute macro to create indicators from async functions
+//!
 //! promtheteus-rocket: includes Prometheus scrap endpoint and Rocket http request metric collection
 //!
 //! prometheus-tonic: includes Tonic grpc request metric collection
 //!
 //! prometheus-r2d2: includes r2d2 connection pool metrics collection
 //!
+//! prometheus-disk: includes disk space metrics collection
+//!
+//! prometheus-tls: includes TLS certificate expiry metrics collection
+//!
+//! prometheus-supervisor: includes supervised background task metrics collection
+//!
+//! supervisor: includes supervisor for critical background tasks, which can be reported by liveness when health is enabled
+//!
 //! ## Examples
 //!
 //! For detailed usage examples, see the examples directory.
@@ -57,7 +133,7 @@
 //!
 //! Contains examples on how to configure health check endpoints and custom health indicators.
 //!
-//! Run with `cargo run --example health --features health,health-diesel`.
+//! Run with `cargo run --example health --features health,health-diesel,health-macros`.
 //!
 //! ### prometheus.rs
 //!
@@ -86,14 +162,41 @@ use rocket::{Build, Rocket};
 
 pub mod actuator;
 
+#[cfg(any(feature = "health", feature = "prometheus-rocket"))]
+pub mod auth;
+
 #[cfg(feature = "health")]
 pub mod health;
 
 #[cfg(feature = "prometheus-rocket")]
 pub mod prometheus;
 
+#[cfg(any(feature = "health-disk", feature = "prometheus-disk"))]
+mod disk;
+
+#[cfg(any(feature = "health-tls", feature = "prometheus-tls"))]
+pub mod tls;
+
+#[cfg(any(feature = "health-tokio", feature = "prometheus-tokio"))]
+pub mod lag;
+
+#[cfg(feature = "supervisor")]
+pub mod supervisor;
+
 pub struct Actuator {
     rocket: Rocket<Build>,
+    base_path: String,
+    configurers: Vec<actuator::Configurer>,
+    #[cfg(feature = "health")]
+    indicators: std::collections::HashMap<String, std::sync::Arc<dyn health::Indicator>>,
+    #[cfg(feature = "health")]
+    gates: health::check::ReadinessGates,
+    #[cfg(feature = "health")]
+    gates_registered: std::sync::atomic::AtomicBool,
+    #[cfg(feature = "health")]
+    readiness: Option<health::check::Registry>,
+    #[cfg(feature = "supervisor")]
+    supervisor: supervisor::TaskSupervisor,
 }
 
 pub fn actuate(rocket: rocket::Rocket<Build>) -> Actuator {
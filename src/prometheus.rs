@@ -1,7 +1,9 @@
+use std::sync::Arc;
+
 use ::prometheus::HistogramVec;
 use prometheus::{opts, HistogramOpts, Registry};
 
-use crate::Actuator;
+use crate::{auth::AccessPolicy, Actuator};
 
 pub mod endpoint;
 pub mod http_request_collector_metrics;
@@ -16,8 +18,21 @@ pub mod r2d2;
 #[cfg(feature = "prometheus-tokio")]
 pub mod tokio;
 
+#[cfg(feature = "prometheus-disk")]
+pub mod disk;
+
+#[cfg(feature = "prometheus-tls")]
+pub mod tls;
+
+#[cfg(feature = "prometheus-supervisor")]
+pub mod supervisor;
+
 pub struct Endpoint {
     registry: Registry,
+    /// The path of the endpoint, relative to the base path of the actuator.
+    path: String,
+    /// The policy requests to the endpoint must satisfy.
+    access_policy: Option<Arc<dyn AccessPolicy>>,
 }
 
 pub struct HttpRequestCollectorConfig {
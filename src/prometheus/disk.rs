@@ -0,0 +1,200 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, Instant},
+};
+
+use prometheus::{
+    core::{Collector, Desc},
+    Gauge,
+};
+
+use crate::disk::{self, DiskUsage};
+
+pub struct DiskSpaceCollectorConfig {
+    path: PathBuf,
+    interval: Duration,
+    free_bytes: prometheus::Opts,
+    total_bytes: prometheus::Opts,
+}
+
+/// Collects the space of the filesystem containing a path. statvfs can
+/// block indefinitely, e.g. on a hung network filesystem, so the space is
+/// sampled on a background thread and scrapes serve the last sample. No
+/// metrics are reported while the sample is older than two intervals.
+pub struct DiskSpaceCollector {
+    path: PathBuf,
+    interval: Duration,
+    sample: Arc<Mutex<Option<Sample>>>,
+    free_bytes: Gauge,
+    total_bytes: Gauge,
+    descs: Vec<Desc>,
+}
+
+struct Sample {
+    /// When the sample was taken.
+    at: Instant,
+    usage: Result<DiskUsage, String>,
+}
+
+impl DiskSpaceCollectorConfig {
+    pub fn default(path: PathBuf) -> Self {
+        let label = path.display().to_string();
+
+        Self {
+            free_bytes: prometheus::opts!(
+                "disk_free_bytes",
+                "disk space available to unprivileged users in bytes",
+                prometheus::labels! {"path" => &label}
+            ),
+            total_bytes: prometheus::opts!(
+                "disk_total_bytes",
+                "disk size in bytes",
+                prometheus::labels! {"path" => &label}
+            ),
+            path,
+            interval: Duration::from_secs(10),
+        }
+    }
+
+    /// Sets the interval the disk space is sampled at. Defaults to 10
+    /// seconds.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+impl DiskSpaceCollector {
+    pub fn new(config: DiskSpaceCollectorConfig) -> Result<Self, prometheus::Error> {
+        let free_bytes = Gauge::with_opts(config.free_bytes)?;
+        let total_bytes = Gauge::with_opts(config.total_bytes)?;
+        let mut descs = vec![];
+
+        descs.extend(free_bytes.desc().into_iter().cloned());
+        descs.extend(total_bytes.desc().into_iter().cloned());
+
+        let sample = Arc::new(Mutex::new(None));
+        let path = config.path.clone();
+        let weak = Arc::downgrade(&sample);
+        std::thread::Builder::new()
+            .name("actuator-disk".to_string())
+            .spawn(move || Self::run(path, config.interval, weak))
+            .map_err(|e| prometheus::Error::Msg(format!("cannot start disk sampler: {}", e)))?;
+
+        let collector = Self {
+            path: config.path,
+            interval: config.interval,
+            sample,
+            free_bytes,
+            total_bytes,
+            descs,
+        };
+
+        Ok(collector)
+    }
+
+    /// Samples the disk space until the collector is dropped.
+    fn run(path: PathBuf, interval: Duration, sample: Weak<Mutex<Option<Sample>>>) {
+        loop {
+            let usage = disk::usage(&path).map_err(|e| e.to_string());
+
+            let Some(sample) = sample.upgrade() else {
+                return;
+            };
+            *sample.lock().unwrap_or_else(|e| e.into_inner()) = Some(Sample {
+                at: Instant::now(),
+                usage,
+            });
+            drop(sample);
+
+            std::thread::sleep(interval);
+        }
+    }
+}
+
+impl Collector for DiskSpaceCollector {
+    fn desc(&self) -> Vec<&Desc> {
+        self.descs.iter().collect()
+    }
+
+    fn collect(&self) -> Vec<prometheus::proto::MetricFamily> {
+        let mut mfs = Vec::new();
+
+        let usage = match &*self.sample.lock().unwrap_or_else(|e| e.into_inner()) {
+            Some(sample) if sample.at.elapsed() > self.interval * 2 => {
+                log::error!(
+                    "cannot collect disk metrics for {}: statvfs has not completed for {:?}",
+                    self.path.display(),
+                    sample.at.elapsed()
+                );
+                return mfs;
+            }
+            Some(Sample {
+                usage: Ok(usage), ..
+            }) => *usage,
+            Some(Sample { usage: Err(e), .. }) => {
+                log::error!(
+                    "cannot collect disk metrics for {}: {}",
+                    self.path.display(),
+                    e
+                );
+                return mfs;
+            }
+            None => return mfs,
+        };
+
+        self.free_bytes.set(usage.free as f64);
+        mfs.extend(self.free_bytes.collect());
+
+        self.total_bytes.set(usage.total as f64);
+        mfs.extend(self.total_bytes.collect());
+
+        mfs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use prometheus::core::Collector;
+
+    use super::{DiskSpaceCollector, DiskSpaceCollectorConfig};
+
+    fn collector(path: &str) -> DiskSpaceCollector {
+        let collector = DiskSpaceCollector::new(
+            DiskSpaceCollectorConfig::default(path.into()).with_interval(Duration::from_secs(10)),
+        )
+        .unwrap();
+
+        let start = Instant::now();
+        while collector.sample.lock().unwrap().is_none() {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        collector
+    }
+
+    #[test]
+    fn serves_sampled_usage() {
+        let collector = collector(std::env::temp_dir().to_str().unwrap());
+
+        let names: Vec<_> = collector
+            .collect()
+            .iter()
+            .map(|mf| mf.get_name().to_string())
+            .collect();
+        assert_eq!(names, vec!["disk_free_bytes", "disk_total_bytes"]);
+    }
+
+    #[test]
+    fn skips_failed_and_stale_samples() {
+        assert!(collector("/nonexistent/actuator").collect().is_empty());
+
+        let collector = collector(std::env::temp_dir().to_str().unwrap());
+        collector.sample.lock().unwrap().as_mut().unwrap().at =
+            Instant::now() - Duration::from_secs(30);
+        assert!(collector.collect().is_empty());
+    }
+}
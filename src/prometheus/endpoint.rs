@@ -1,34 +1,68 @@
+use std::sync::Arc;
+
 use prometheus::{Registry, TextEncoder};
 use rocket::{get, http::Status, routes, Build, Rocket, State};
 
-use crate::actuator::RocketConfigurerer;
+use crate::{
+    actuator::{join_path, RocketConfigurerer},
+    auth::{AccessPolicy, Authorized, Denied, Protected},
+};
 
 use super::Endpoint;
 
-#[get("/metrics")]
-fn metrics(registry: &State<Registry>) -> Result<String, rocket::response::status::Custom<String>> {
+#[get("/")]
+fn metrics(
+    auth: Result<Authorized<Endpoint>, Denied>,
+    endpoint: &State<Endpoint>,
+) -> Result<Result<String, rocket::response::status::Custom<String>>, Denied> {
+    auth?;
+
     let mut body = String::new();
     let encoder = TextEncoder::new();
-    encoder
-        .encode_utf8(&registry.gather(), &mut body)
+    let encoded = encoder
+        .encode_utf8(&endpoint.registry.gather(), &mut body)
         .map_err(|err| {
             rocket::response::status::Custom(
                 Status::InternalServerError,
                 format!("Could not collect metrics: {}", err),
             )
-        })?;
+        });
 
-    Ok(body)
+    Ok(encoded.map(|_| body))
 }
 
 impl Endpoint {
     pub fn new(registry: Registry) -> Self {
-        Self { registry }
+        Self {
+            registry,
+            path: "/metrics".to_string(),
+            access_policy: None,
+        }
+    }
+
+    /// Requires requests to the metrics endpoint to satisfy the given policy.
+    pub fn with_access_policy(mut self, policy: impl AccessPolicy + 'static) -> Self {
+        self.access_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Sets the path of the metrics endpoint, relative to the base path of
+    /// the actuator. Defaults to `/metrics`.
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path = path.to_string();
+        self
+    }
+}
+
+impl Protected for Endpoint {
+    fn access_policy(&self) -> Option<&Arc<dyn AccessPolicy>> {
+        self.access_policy.as_ref()
     }
 }
 
 impl RocketConfigurerer for Endpoint {
-    fn configure(self, rocket: Rocket<Build>) -> Rocket<Build> {
-        rocket.manage(self.registry).mount("/", routes![metrics])
+    fn configure(self, rocket: Rocket<Build>, base_path: &str) -> Rocket<Build> {
+        let path = join_path(base_path, &self.path);
+        rocket.manage(self).mount(path, routes![metrics])
     }
 }
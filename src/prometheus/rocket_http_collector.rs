@@ -19,7 +19,7 @@ impl RocketHttpCollector {
 
 #[async_trait::async_trait]
 impl RocketConfigurerer for RocketHttpCollector {
-    fn configure(self, rocket: Rocket<Build>) -> Rocket<Build> {
+    fn configure(self, rocket: Rocket<Build>, _base_path: &str) -> Rocket<Build> {
         rocket.attach(RocketHttpCollectorFairing {
             metrics: self.metrics.clone(),
         })
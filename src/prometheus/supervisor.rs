@@ -0,0 +1,127 @@
+use prometheus::{
+    core::{Collector, Desc},
+    GaugeVec, IntCounterVec,
+};
+
+use crate::supervisor::TaskSupervisor;
+
+pub struct TaskSupervisorCollectorConfig {
+    supervisor: TaskSupervisor,
+    task_state: prometheus::Opts,
+    task_restarts: prometheus::Opts,
+}
+
+pub struct TaskSupervisorCollector {
+    supervisor: TaskSupervisor,
+    task_state: GaugeVec,
+    task_restarts: IntCounterVec,
+    descs: Vec<Desc>,
+}
+
+const STATES: [&str; 4] = ["running", "exited", "cancelled", "panicked"];
+
+impl TaskSupervisorCollectorConfig {
+    pub fn default(supervisor: TaskSupervisor) -> Self {
+        Self {
+            supervisor,
+            task_state: prometheus::opts!(
+                "supervised_task_state",
+                "state of supervised tasks, 1 for the current state"
+            ),
+            task_restarts: prometheus::opts!(
+                "supervised_task_restarts_total",
+                "number of restarts of supervised tasks"
+            ),
+        }
+    }
+}
+
+impl TaskSupervisorCollector {
+    pub fn new(config: TaskSupervisorCollectorConfig) -> Result<Self, prometheus::Error> {
+        let task_state = GaugeVec::new(config.task_state, &["name", "critical", "state"])?;
+        let task_restarts = IntCounterVec::new(config.task_restarts, &["name"])?;
+        let mut descs = vec![];
+
+        descs.extend(task_state.desc().into_iter().cloned());
+        descs.extend(task_restarts.desc().into_iter().cloned());
+
+        let collector = Self {
+            supervisor: config.supervisor,
+            task_state,
+            task_restarts,
+            descs,
+        };
+
+        Ok(collector)
+    }
+}
+
+impl Collector for TaskSupervisorCollector {
+    fn desc(&self) -> Vec<&Desc> {
+        self.descs.iter().collect()
+    }
+
+    fn collect(&self) -> Vec<prometheus::proto::MetricFamily> {
+        let mut mfs = Vec::new();
+
+        // Restart counters are kept, as counters must not decrease
+        self.task_state.reset();
+
+        for task in self.supervisor.tasks() {
+            let critical = task.critical.to_string();
+            for state in STATES {
+                let value = if task.state.as_str() == state {
+                    1.0
+                } else {
+                    0.0
+                };
+                self.task_state
+                    .with_label_values(&[task.name.as_str(), critical.as_str(), state])
+                    .set(value);
+            }
+
+            let restarts = self.task_restarts.with_label_values(&[task.name.as_str()]);
+            restarts.inc_by(task.restarts.saturating_sub(restarts.get()));
+        }
+
+        mfs.extend(self.task_state.collect());
+        mfs.extend(self.task_restarts.collect());
+
+        mfs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prometheus::core::Collector;
+
+    use crate::supervisor::TaskSupervisor;
+
+    use super::{TaskSupervisorCollector, TaskSupervisorCollectorConfig};
+
+    fn restarts(collector: &TaskSupervisorCollector) -> f64 {
+        let families = collector.collect();
+        let family = families
+            .iter()
+            .find(|f| f.get_name() == "supervised_task_restarts_total")
+            .unwrap();
+        family.get_metric()[0].get_counter().get_value()
+    }
+
+    #[tokio::test]
+    async fn counts_restarts() {
+        let supervisor = TaskSupervisor::default();
+        let collector = TaskSupervisorCollector::new(TaskSupervisorCollectorConfig::default(
+            supervisor.clone(),
+        ))
+        .unwrap();
+
+        supervisor.spawn("worker", true, std::future::pending::<()>());
+        assert_eq!(restarts(&collector), 0.0);
+
+        supervisor.spawn("worker", true, std::future::pending::<()>());
+        supervisor.spawn("worker", true, std::future::pending::<()>());
+        assert_eq!(restarts(&collector), 2.0);
+        assert_eq!(restarts(&collector), 2.0);
+    }
+}
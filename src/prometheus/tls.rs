@@ -0,0 +1,158 @@
+use std::path::PathBuf;
+
+use prometheus::{
+    core::{Collector, Desc},
+    GaugeVec,
+};
+
+use crate::tls::CertificateSource;
+
+pub struct CertificateExpiryCollectorConfig {
+    sources: Vec<CertificateSource>,
+    expiry_timestamp_seconds: prometheus::Opts,
+}
+
+pub struct CertificateExpiryCollector {
+    sources: Vec<CertificateSource>,
+    expiry_timestamp_seconds: GaugeVec,
+    descs: Vec<Desc>,
+}
+
+impl CertificateExpiryCollectorConfig {
+    pub fn default(paths: Vec<PathBuf>) -> Self {
+        Self::from_sources(paths.into_iter().map(CertificateSource::Path).collect())
+    }
+
+    /// Collects the expiry of the certificates in the given PEM files or
+    /// PEM data, e.g. [`CertificateSource::from_rocket_config`].
+    pub fn from_sources(sources: Vec<CertificateSource>) -> Self {
+        Self {
+            sources,
+            expiry_timestamp_seconds: prometheus::opts!(
+                "tls_certificate_expiry_timestamp_seconds",
+                "expiry time of tls certificates as unix timestamp in seconds"
+            ),
+        }
+    }
+}
+
+impl CertificateExpiryCollector {
+    pub fn new(config: CertificateExpiryCollectorConfig) -> Result<Self, prometheus::Error> {
+        let expiry_timestamp_seconds =
+            GaugeVec::new(config.expiry_timestamp_seconds, &["path", "subject"])?;
+        let mut descs = vec![];
+
+        descs.extend(expiry_timestamp_seconds.desc().into_iter().cloned());
+
+        let collector = Self {
+            sources: config.sources,
+            expiry_timestamp_seconds,
+            descs,
+        };
+
+        Ok(collector)
+    }
+}
+
+impl Collector for CertificateExpiryCollector {
+    fn desc(&self) -> Vec<&Desc> {
+        self.descs.iter().collect()
+    }
+
+    fn collect(&self) -> Vec<prometheus::proto::MetricFamily> {
+        // Drop certificates that are no longer in the files
+        self.expiry_timestamp_seconds.reset();
+
+        for source in &self.sources {
+            let certificates = match source.load() {
+                Ok(certificates) => certificates,
+                Err(e) => {
+                    log::error!("cannot collect tls certificate metrics: {}", e);
+                    continue;
+                }
+            };
+
+            let path = source.describe();
+            for certificate in certificates {
+                self.expiry_timestamp_seconds
+                    .with_label_values(&[path.as_str(), certificate.subject.as_str()])
+                    .set(certificate.not_after as f64);
+            }
+        }
+
+        self.expiry_timestamp_seconds.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use prometheus::core::Collector;
+    use rocket::figment::{
+        providers::{Format, Toml},
+        Figment,
+    };
+
+    use crate::tls::CertificateSource;
+
+    use super::{CertificateExpiryCollector, CertificateExpiryCollectorConfig};
+
+    fn cert(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/certs")
+            .join(name)
+    }
+
+    /// Returns the path label and the expiry of the collected certificates.
+    fn collected(sources: Vec<CertificateSource>) -> Vec<(String, f64)> {
+        let collector = CertificateExpiryCollector::new(
+            CertificateExpiryCollectorConfig::from_sources(sources),
+        )
+        .unwrap();
+
+        collector.collect()[0]
+            .get_metric()
+            .iter()
+            .map(|m| {
+                let path = m
+                    .get_label()
+                    .iter()
+                    .find(|l| l.get_name() == "path")
+                    .unwrap();
+                (path.get_value().to_string(), m.get_gauge().get_value())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn collects_expiry_of_all_sources() {
+        let pem = std::fs::read(cert("valid.pem")).unwrap();
+        let metrics = collected(vec![
+            CertificateSource::Path(cert("valid.pem")),
+            CertificateSource::Pem(pem),
+            CertificateSource::Path(cert("missing.pem")),
+        ]);
+
+        let paths: Vec<_> = metrics.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                cert("valid.pem").display().to_string().as_str(),
+                "inline PEM"
+            ]
+        );
+        assert_eq!(metrics[0].1, metrics[1].1);
+        assert!(metrics[0].1 > 0.0);
+    }
+
+    #[test]
+    fn collects_rocket_certificates() {
+        let figment = Figment::from(Toml::file(cert("rocket.toml")));
+        let source = CertificateSource::from_rocket_config(&figment).unwrap();
+
+        let metrics = collected(vec![source]);
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].0, cert("valid.pem").display().to_string());
+    }
+}
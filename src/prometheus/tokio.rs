@@ -3,6 +3,8 @@ use prometheus::{
     Gauge,
 };
 
+use crate::lag::LagMonitor;
+
 pub struct RuntimeMetricCollectorConfig {
     runtime: tokio::runtime::Handle,
     workers_total: prometheus::Opts,
@@ -14,6 +16,11 @@ pub struct RuntimeMetricCollectorConfig {
     blocking_queue_depth: prometheus::Opts,
 }
 
+pub struct EventLoopLagCollectorConfig {
+    monitor: LagMonitor,
+    event_loop_lag_seconds: prometheus::Opts,
+}
+
 pub struct RuntimeMetricCollector {
     runtime: tokio::runtime::Handle,
     workers_total: Gauge,
@@ -26,6 +33,12 @@ pub struct RuntimeMetricCollector {
     descs: Vec<Desc>,
 }
 
+pub struct EventLoopLagCollector {
+    monitor: LagMonitor,
+    event_loop_lag_seconds: Gauge,
+    descs: Vec<Desc>,
+}
+
 impl RuntimeMetricCollectorConfig {
     pub fn default(runtime: tokio::runtime::Handle) -> Self {
         let runtime_id = &runtime.id().to_string();
@@ -146,3 +159,77 @@ impl Collector for RuntimeMetricCollector {
         mfs
     }
 }
+
+impl EventLoopLagCollectorConfig {
+    pub fn default(monitor: LagMonitor) -> Self {
+        Self {
+            monitor,
+            event_loop_lag_seconds: prometheus::opts!(
+                "tokio_event_loop_lag_seconds",
+                "tokio_event_loop_lag_seconds"
+            ),
+        }
+    }
+}
+
+impl EventLoopLagCollector {
+    pub fn new(config: EventLoopLagCollectorConfig) -> Result<Self, prometheus::Error> {
+        let event_loop_lag_seconds = Gauge::with_opts(config.event_loop_lag_seconds)?;
+
+        let mut descs = vec![];
+
+        descs.extend(event_loop_lag_seconds.desc().into_iter().cloned());
+
+        let collector = Self {
+            monitor: config.monitor,
+            event_loop_lag_seconds,
+            descs,
+        };
+
+        Ok(collector)
+    }
+}
+
+impl Collector for EventLoopLagCollector {
+    fn desc(&self) -> Vec<&Desc> {
+        self.descs.iter().collect()
+    }
+
+    fn collect(&self) -> Vec<prometheus::proto::MetricFamily> {
+        self.event_loop_lag_seconds
+            .set(self.monitor.lag().as_secs_f64());
+
+        self.event_loop_lag_seconds.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use prometheus::core::Collector;
+
+    use crate::lag::LagMonitor;
+
+    use super::{EventLoopLagCollector, EventLoopLagCollectorConfig};
+
+    fn lag(collector: &EventLoopLagCollector) -> f64 {
+        let families = collector.collect();
+        assert_eq!(families[0].get_name(), "tokio_event_loop_lag_seconds");
+        families[0].get_metric()[0].get_gauge().get_value()
+    }
+
+    #[tokio::test]
+    async fn reports_event_loop_lag() {
+        let collector = EventLoopLagCollector::new(EventLoopLagCollectorConfig::default(
+            LagMonitor::start(Duration::from_millis(10)),
+        ))
+        .unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(lag(&collector) < 0.04);
+
+        // Blocks the current thread runtime of the test
+        std::thread::sleep(Duration::from_millis(200));
+        assert!(lag(&collector) >= 0.15);
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use tokio::task::AbortHandle;
+
+/// Tracks critical background tasks, e.g. consumers or schedulers, so that
+/// their exit or panic does not go unnoticed. Cloned supervisors share the
+/// same tasks.
+#[derive(Clone, Default)]
+pub struct TaskSupervisor {
+    tasks: Arc<Mutex<BTreeMap<String, Task>>>,
+}
+
+struct Task {
+    critical: bool,
+    state: TaskState,
+    restarts: u64,
+    /// Incremented on every spawn, so that a replaced task does not
+    /// overwrite the state of its replacement when it completes.
+    generation: u64,
+    abort: AbortHandle,
+}
+
+/// The state of a supervised task.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TaskState {
+    Running,
+    /// The task returned.
+    Exited,
+    /// The task was aborted.
+    Cancelled,
+    /// The task panicked with the given message.
+    Panicked(String),
+}
+
+/// A snapshot of a supervised task.
+#[derive(Clone, Debug)]
+pub struct TaskStatus {
+    /// The name of the task.
+    pub name: String,
+    /// Whether the task must keep running for the service to be alive.
+    pub critical: bool,
+    /// The state of the task.
+    pub state: TaskState,
+    /// How many times a task was spawned again under the same name.
+    pub restarts: u64,
+}
+
+impl TaskState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Exited => "exited",
+            Self::Cancelled => "cancelled",
+            Self::Panicked(_) => "panicked",
+        }
+    }
+}
+
+impl TaskSupervisor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawns the given future on the current tokio runtime and tracks it
+    /// under the given name. Spawning a task with the name of a previous
+    /// task aborts and replaces it, counting as a restart. Must be called
+    /// within a tokio runtime.
+    pub fn spawn<F>(&self, name: &str, critical: bool, future: F) -> AbortHandle
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+
+        let handle = tokio::spawn(future);
+        let abort = handle.abort_handle();
+
+        let (generation, restarts) = match tasks.get(name) {
+            Some(previous) => {
+                previous.abort.abort();
+                (previous.generation + 1, previous.restarts + 1)
+            }
+            None => (0, 0),
+        };
+        tasks.insert(
+            name.to_string(),
+            Task {
+                critical,
+                state: TaskState::Running,
+                restarts,
+                generation,
+                abort: abort.clone(),
+            },
+        );
+
+        let supervisor = self.clone();
+        let name = name.to_string();
+        tokio::spawn(async move {
+            let state = match handle.await {
+                Ok(_) => TaskState::Exited,
+                Err(e) if e.is_panic() => TaskState::Panicked(panic_message(e.into_panic())),
+                Err(_) => TaskState::Cancelled,
+            };
+
+            match &state {
+                TaskState::Panicked(message) => {
+                    log::error!("supervised task {} panicked: {}", name, message)
+                }
+                state => log::warn!("supervised task {} {}", name, state.as_str()),
+            }
+
+            let mut tasks = supervisor.tasks.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(task) = tasks.get_mut(&name) {
+                if task.generation == generation {
+                    task.state = state;
+                }
+            }
+        });
+
+        abort
+    }
+
+    /// Aborts the task with the given name, returning whether it was
+    /// tracked.
+    pub fn abort(&self, name: &str) -> bool {
+        let tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+
+        match tasks.get(name) {
+            Some(task) => {
+                task.abort.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns a snapshot of the tracked tasks, ordered by name.
+    pub fn tasks(&self) -> Vec<TaskStatus> {
+        self.tasks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(name, task)| TaskStatus {
+                name: name.clone(),
+                critical: task.critical,
+                state: task.state.clone(),
+                restarts: task.restarts,
+            })
+            .collect()
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
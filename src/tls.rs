@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use rocket::figment::{value::magic::RelativePathBuf, Figment};
+use x509_parser::pem::Pem;
+
+/// Where certificates are loaded from, by the certificate health indicator
+/// and the certificate expiry collector.
+pub enum CertificateSource {
+    /// A PEM file, read on every check.
+    Path(PathBuf),
+    /// PEM data, e.g. certificates configured inline.
+    Pem(Vec<u8>),
+}
+
+/// A certificate loaded from a PEM file.
+pub(crate) struct Certificate {
+    /// The subject of the certificate.
+    pub(crate) subject: String,
+    /// The end of the validity period as unix timestamp in seconds.
+    pub(crate) not_after: i64,
+}
+
+impl CertificateSource {
+    /// Returns the certificate chain configured for Rocket's TLS listener
+    /// in `tls.certs`, either as a path, relative to the configuration file
+    /// that sets it, or as inline PEM data.
+    pub fn from_rocket_config(figment: &Figment) -> Result<Self, String> {
+        match figment.extract_inner::<RelativePathBuf>("tls.certs") {
+            Ok(path) => Ok(Self::Path(path.relative())),
+            Err(e) => figment
+                .extract_inner::<Vec<u8>>("tls.certs")
+                .map(Self::Pem)
+                .map_err(|_| format!("could not find tls certificate configuration: {}", e)),
+        }
+    }
+
+    /// Describes the source in messages and labels.
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            Self::Path(path) => path.display().to_string(),
+            Self::Pem(_) => "inline PEM".to_string(),
+        }
+    }
+
+    /// Loads all certificates of the source, e.g. a certificate chain.
+    #[cfg(feature = "prometheus-tls")]
+    pub(crate) fn load(&self) -> Result<Vec<Certificate>, String> {
+        match self {
+            Self::Path(path) => {
+                let data = std::fs::read(path)
+                    .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+                parse(&data, &self.describe())
+            }
+            Self::Pem(data) => parse(data, &self.describe()),
+        }
+    }
+
+    /// Loads all certificates of the source without blocking the runtime.
+    #[cfg(feature = "health-tls")]
+    pub(crate) async fn load_async(&self) -> Result<Vec<Certificate>, String> {
+        match self {
+            Self::Path(path) => {
+                let data = tokio::fs::read(path)
+                    .await
+                    .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+                parse(&data, &self.describe())
+            }
+            Self::Pem(data) => parse(data, &self.describe()),
+        }
+    }
+}
+
+/// Parses all certificates of the given PEM data, naming the source of the
+/// data in errors.
+pub(crate) fn parse(data: &[u8], source: &str) -> Result<Vec<Certificate>, String> {
+    let mut certificates = vec![];
+    for pem in Pem::iter_from_buffer(data) {
+        let pem = pem.map_err(|e| format!("invalid PEM in {}: {}", source, e))?;
+        if pem.label != "CERTIFICATE" {
+            continue;
+        }
+
+        let certificate = pem
+            .parse_x509()
+            .map_err(|e| format!("invalid certificate in {}: {}", source, e))?;
+
+        certificates.push(Certificate {
+            subject: certificate.subject().to_string(),
+            not_after: certificate.validity().not_after.timestamp(),
+        });
+    }
+
+    if certificates.is_empty() {
+        return Err(format!("no certificate found in {}", source));
+    }
+
+    Ok(certificates)
+}
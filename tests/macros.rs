@@ -0,0 +1,10 @@
+// trybuild overrides the rustflags of the build, dropping the
+// `tokio_unstable` cfg prometheus-tokio requires
+#![cfg(all(feature = "health-macros", not(feature = "prometheus-tokio")))]
+
+#[test]
+fn indicator() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass/*.rs");
+    t.compile_fail("tests/ui/fail/*.rs");
+}
@@ -0,0 +1,8 @@
+use valensas_actuator::health::indicator;
+
+#[indicator(title = "database")]
+async fn database() -> Result<(), String> {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use valensas_actuator::health::indicator;
+
+#[indicator]
+async fn generic<T: Clone + Send + Sync + 'static>(value: T) -> Result<(), String> {
+    Ok(())
+}
+
+fn main() {}
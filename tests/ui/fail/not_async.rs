@@ -0,0 +1,8 @@
+use valensas_actuator::health::indicator;
+
+#[indicator]
+fn blocking() -> Result<(), String> {
+    Ok(())
+}
+
+fn main() {}
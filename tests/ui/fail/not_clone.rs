@@ -0,0 +1,11 @@
+use valensas_actuator::health::indicator;
+
+struct Connection;
+
+#[indicator]
+async fn database(connection: Connection) -> Result<(), String> {
+    drop(connection);
+    Ok(())
+}
+
+fn main() {}
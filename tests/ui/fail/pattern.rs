@@ -0,0 +1,8 @@
+use valensas_actuator::health::indicator;
+
+#[indicator]
+async fn destructured((a, b): (u32, u32)) -> Result<(), String> {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,54 @@
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+
+use valensas_actuator::health::{indicator, IndicatorResult, IndicatorStatus};
+
+#[indicator]
+async fn no_arguments() -> Result<(), String> {
+    Ok(())
+}
+
+#[indicator(name = "counter")]
+async fn counted(count: Arc<AtomicU32>, limit: u32) -> Result<(), String> {
+    if count.fetch_add(1, Ordering::Relaxed) < limit {
+        Ok(())
+    } else {
+        Err("limit reached".to_string())
+    }
+}
+
+#[indicator]
+async fn mutable(mut attempts: Vec<u32>) -> IndicatorResult {
+    attempts.push(1);
+    IndicatorResult::up().with_detail("attempts", attempts.len())
+}
+
+#[indicator]
+async fn by_reference(ref name: String) -> Result<(), String> {
+    Err(name.clone())
+}
+
+fn main() {
+    futures::executor::block_on(async {
+        let indicator = no_arguments();
+        assert_eq!(indicator.name(), "no_arguments");
+        assert_eq!(indicator.check().await, Ok(()));
+
+        let indicator = counted(Arc::new(AtomicU32::new(0)), 1);
+        assert_eq!(indicator.name(), "counter");
+        assert_eq!(indicator.check().await, Ok(()));
+        assert_eq!(indicator.check().await, Err("limit reached".to_string()));
+
+        let indicator = mutable(vec![]);
+        let result = indicator.health().await;
+        assert_eq!(result.status(), IndicatorStatus::Up);
+        // The arguments are cloned for every check
+        assert_eq!(result.details()["attempts"], 1);
+        assert_eq!(indicator.health().await.details()["attempts"], 1);
+
+        let indicator = by_reference("down".to_string());
+        assert_eq!(indicator.check().await, Err("down".to_string()));
+    });
+}